@@ -1,59 +1,207 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use chrono::Utc;
+use futures_util::StreamExt;
+use tokio::sync::{broadcast, RwLock};
 use tokio::time::{self, Duration};
 use tracing::{info, warn};
 
-use crate::models::k8s::{Node, Pod};
+use crate::config::{HealthConfig, SecurityConfig};
+use crate::models::k8s::{Node, NodeWatchEvent, Pod, PodWatchEvent};
 use crate::models::views::{ClusterSummary, NodeSummary};
-
-use super::NodeClient;
+use crate::scheduler::{self, Scheduler};
+
+use super::store::ClusterStore;
+use super::{LogStream, NodeClient};
+
+/// Capacity of the merged pod-watch broadcast channel. A slow SSE client that
+/// falls behind this many events gets a `Lagged` error and resyncs rather
+/// than holding up delivery to everyone else.
+const POD_WATCH_CHANNEL_CAPACITY: usize = 1024;
+/// Capacity of the node-watch broadcast channel. Node state changes far less
+/// often than pod state, so this can be much smaller than the pod channel.
+const NODE_WATCH_CHANNEL_CAPACITY: usize = 256;
+/// How often a BOOKMARK carrying the latest resourceVersion is emitted on
+/// the pod/node watch channels, so reconnecting clients have a recent
+/// version to resume from even during a quiet period.
+const WATCH_BOOKMARK_INTERVAL: Duration = Duration::from_secs(30);
 
 pub struct Aggregator {
     clients: RwLock<HashMap<String, Arc<NodeClient>>>,
+    pod_watch_tx: broadcast::Sender<PodWatchEvent>,
+    node_watch_tx: broadcast::Sender<NodeWatchEvent>,
+    /// Last-seen signature per node, so `run_node_watcher` only emits events
+    /// for nodes whose readiness/pressure/annotations actually changed.
+    node_snapshot: RwLock<HashMap<String, NodeSignature>>,
+    store: ClusterStore,
+    scheduler: Box<dyn Scheduler>,
+    health_config: HealthConfig,
+    /// Default security config applied to nodes registered at runtime via
+    /// `add_client`, which have no `NodeDef` of their own to carry a
+    /// per-node override. Statically configured nodes resolve their own
+    /// effective config via `NodeDef::effective_security` before reaching
+    /// the aggregator at all.
+    security: Option<SecurityConfig>,
+    /// Monotonic counter stamped onto every pod/node watch event's
+    /// `metadata.resourceVersion`, so clients can resume a watch from where
+    /// they left off instead of re-listing.
+    resource_version: AtomicU64,
+    /// Per-node watch tasks, so a node that leaves membership at runtime has
+    /// its watcher stopped rather than left running against a stale client.
+    watch_tasks: RwLock<HashMap<String, tokio::task::JoinHandle<()>>>,
+    /// Set once `run_pod_watcher` starts, so nodes registered afterwards via
+    /// `add_client` can have a watch task spun up for them immediately.
+    pod_watch_shutdown: RwLock<Option<tokio::sync::watch::Receiver<()>>>,
+    /// Ring buffer of recent real (non-bookmark) pod-watch events, so a
+    /// client resuming from `?resourceVersion=` can replay what it missed
+    /// instead of the broadcast channel's history-free semantics silently
+    /// dropping it. Bounded to `POD_WATCH_CHANNEL_CAPACITY`; a resume request
+    /// older than everything still buffered falls back to a full relist.
+    pod_event_log: RwLock<VecDeque<PodWatchEvent>>,
+    /// Node-watch equivalent of `pod_event_log`.
+    node_event_log: RwLock<VecDeque<NodeWatchEvent>>,
 }
 
 impl Aggregator {
-    pub fn new(clients: Vec<NodeClient>) -> Self {
+    pub fn new(
+        clients: Vec<NodeClient>,
+        scheduler_strategy: &str,
+        health_config: HealthConfig,
+        security: Option<SecurityConfig>,
+    ) -> Self {
         let mut m = HashMap::new();
         for c in clients {
             m.insert(c.name.clone(), Arc::new(c));
         }
+        let (pod_watch_tx, _) = broadcast::channel(POD_WATCH_CHANNEL_CAPACITY);
+        let (node_watch_tx, _) = broadcast::channel(NODE_WATCH_CHANNEL_CAPACITY);
         Self {
             clients: RwLock::new(m),
+            pod_watch_tx,
+            node_watch_tx,
+            node_snapshot: RwLock::new(HashMap::new()),
+            store: ClusterStore::new(),
+            scheduler: scheduler::from_name(scheduler_strategy),
+            health_config,
+            security,
+            resource_version: AtomicU64::new(0),
+            watch_tasks: RwLock::new(HashMap::new()),
+            pod_watch_shutdown: RwLock::new(None),
+            pod_event_log: RwLock::new(VecDeque::with_capacity(POD_WATCH_CHANNEL_CAPACITY)),
+            node_event_log: RwLock::new(VecDeque::with_capacity(NODE_WATCH_CHANNEL_CAPACITY)),
         }
     }
 
-    pub async fn list_all_pods(&self) -> Result<Vec<Pod>, Box<dyn std::error::Error + Send + Sync>> {
-        let clients = self.snapshot().await;
+    /// Registers a new node at runtime, or refreshes an existing one's
+    /// heartbeat if already registered. Starts a watch task for a genuinely
+    /// new node if the pod watcher is already running.
+    pub async fn add_client(self: &Arc<Self>, name: String, address: String) {
+        {
+            let clients = self.clients.read().await;
+            if let Some(existing) = clients.get(&name) {
+                existing.touch_heartbeat();
+                return;
+            }
+        }
 
-        let mut all_pods = Vec::new();
-        let mut handles = Vec::new();
+        let client = match NodeClient::new(
+            name.clone(),
+            address,
+            self.health_config.clone(),
+            self.security.as_ref(),
+        ) {
+            Ok(client) => Arc::new(client),
+            Err(e) => {
+                warn!("failed to register node {}: {}", name, e);
+                return;
+            }
+        };
+        self.clients.write().await.insert(name.clone(), client.clone());
+        info!("node {} registered", name);
 
-        for client in clients {
-            let c = client.clone();
-            handles.push(tokio::spawn(async move {
-                match c.list_pods().await {
-                    Ok(list) => Some((c.name.clone(), list)),
-                    Err(e) => {
-                        warn!("error listing pods from {}: {}", c.name, e);
-                        None
-                    }
-                }
-            }));
+        let shutdown = self.pod_watch_shutdown.read().await.clone();
+        if let Some(shutdown) = shutdown {
+            self.spawn_watch_task(client, shutdown).await;
         }
+    }
 
-        for handle in handles {
-            if let Ok(Some((node_name, list))) = handle.await {
-                for mut pod in list.items {
-                    let annotations = pod.metadata.annotations.get_or_insert_with(HashMap::new);
-                    annotations.insert("mkube.io/node".to_string(), node_name.clone());
-                    all_pods.push(pod);
+    /// Evicts a node from the live set: removes its client, drops its cached
+    /// pods, and stops its watch task.
+    pub async fn remove_client(&self, name: &str) {
+        self.clients.write().await.remove(name);
+        self.store.invalidate_node(name).await;
+        if let Some(handle) = self.watch_tasks.write().await.remove(name) {
+            handle.abort();
+        }
+        info!("node {} removed from membership", name);
+    }
+
+    /// Evicts any runtime-registered node that has missed heartbeats
+    /// (health-check pings or `/register` calls) for longer than `ttl`. This
+    /// is distinct from the health-flag mechanism: an `Unhealthy` node is
+    /// still a cluster member that might recover, whereas a heartbeat-stale
+    /// node is dropped from membership entirely.
+    ///
+    /// Nodes declared in the static config file are exempt: they have no
+    /// `/register` call to refresh their heartbeat, so TTL eviction would
+    /// permanently drop one after any outage longer than `ttl` and it could
+    /// never rejoin. They stay in membership and are left to the health-flag
+    /// state machine (`Unhealthy` -> `Probing` -> `Healthy`) to recover.
+    pub async fn run_membership_evictor(
+        self: Arc<Self>,
+        ttl: Duration,
+        mut shutdown: tokio::sync::watch::Receiver<()>,
+    ) {
+        let mut interval = time::interval(Duration::from_secs(10));
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    self.evict_stale(ttl).await;
+                }
+                _ = shutdown.changed() => {
+                    info!("membership evictor shutting down");
+                    return;
                 }
             }
         }
+    }
 
-        Ok(all_pods)
+    async fn evict_stale(&self, ttl: Duration) {
+        let now = Utc::now();
+        let stale: Vec<String> = self
+            .snapshot()
+            .await
+            .into_iter()
+            .filter(|c| {
+                !c.is_static()
+                    && now
+                        .signed_duration_since(c.last_heartbeat())
+                        .to_std()
+                        .map(|age| age > ttl)
+                        .unwrap_or(false)
+            })
+            .map(|c| c.name.clone())
+            .collect();
+
+        for name in stale {
+            warn!("evicting node {} after missed heartbeats", name);
+            self.remove_client(&name).await;
+        }
+    }
+
+    /// Subscribes to the merged, multi-node pod watch feed. Each event is
+    /// already tagged with the owning node via the `mkube.io/node` annotation.
+    pub fn subscribe_pod_watch(&self) -> broadcast::Receiver<PodWatchEvent> {
+        self.pod_watch_tx.subscribe()
+    }
+
+    /// Answers from the informer-style `ClusterStore` cache rather than
+    /// fanning a fresh `list_pods` call out to every node.
+    pub async fn list_all_pods(&self) -> Result<Vec<Pod>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.store.all_pods().await)
     }
 
     pub async fn list_all_nodes(
@@ -86,13 +234,202 @@ impl Aggregator {
         Ok(nodes)
     }
 
+    /// Subscribes to the node-events feed powering `/ui/events/nodes`. Each
+    /// event is a delta (`ADDED`/`MODIFIED`/`DELETED`) against the last poll,
+    /// or a periodic `BOOKMARK`, not a
+    /// full resync, so subscribers should already hold an initial snapshot
+    /// from `list_all_nodes`.
+    pub fn subscribe_node_watch(&self) -> broadcast::Receiver<NodeWatchEvent> {
+        self.node_watch_tx.subscribe()
+    }
+
+    /// Advances and returns the next watch resourceVersion, as a string since
+    /// that's how Kubernetes itself represents it over the wire.
+    fn next_resource_version(&self) -> String {
+        (self.resource_version.fetch_add(1, Ordering::SeqCst) + 1).to_string()
+    }
+
+    /// The most recently issued resourceVersion, for stamping periodic
+    /// BOOKMARK events without minting a new one.
+    fn current_resource_version(&self) -> String {
+        self.resource_version.load(Ordering::SeqCst).to_string()
+    }
+
+    /// Appends a real pod-watch event to the replay buffer, evicting the
+    /// oldest entry once it's full.
+    async fn record_pod_event(&self, ev: &PodWatchEvent) {
+        let mut log = self.pod_event_log.write().await;
+        if log.len() >= POD_WATCH_CHANNEL_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(ev.clone());
+    }
+
+    /// Appends a real node-watch event to the replay buffer, evicting the
+    /// oldest entry once it's full.
+    async fn record_node_event(&self, ev: &NodeWatchEvent) {
+        let mut log = self.node_event_log.write().await;
+        if log.len() >= NODE_WATCH_CHANNEL_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(ev.clone());
+    }
+
+    /// Returns buffered pod-watch events newer than `since`, so a client
+    /// resuming with `?resourceVersion=` gets everything it missed instead
+    /// of only live events from the moment it reconnects. Returns `None` if
+    /// `since` predates everything still buffered (the ring wrapped past
+    /// it), so the caller knows to fall back to a full relist rather than
+    /// silently skip the gap.
+    pub async fn pod_events_since(&self, since: &str) -> Option<Vec<PodWatchEvent>> {
+        let since: u64 = since.parse().ok()?;
+        let log = self.pod_event_log.read().await;
+        if let Some(oldest) = log.front() {
+            let oldest_version: u64 = oldest.object.metadata.resource_version.parse().unwrap_or(0);
+            if oldest_version > since + 1 {
+                return None;
+            }
+        }
+        Some(
+            log.iter()
+                .filter(|ev| {
+                    ev.object
+                        .metadata
+                        .resource_version
+                        .parse::<u64>()
+                        .unwrap_or(0)
+                        > since
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Node-watch equivalent of [`Aggregator::pod_events_since`].
+    pub async fn node_events_since(&self, since: &str) -> Option<Vec<NodeWatchEvent>> {
+        let since: u64 = since.parse().ok()?;
+        let log = self.node_event_log.read().await;
+        if let Some(oldest) = log.front() {
+            let oldest_version: u64 = oldest.object.metadata.resource_version.parse().unwrap_or(0);
+            if oldest_version > since + 1 {
+                return None;
+            }
+        }
+        Some(
+            log.iter()
+                .filter(|ev| {
+                    ev.object
+                        .metadata
+                        .resource_version
+                        .parse::<u64>()
+                        .unwrap_or(0)
+                        > since
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Polls node status on an interval and emits watch events only for
+    /// nodes whose readiness, pressure conditions, or uptime/cpu-load
+    /// annotations actually changed since the last poll.
+    pub async fn run_node_watcher(self: Arc<Self>, mut shutdown: tokio::sync::watch::Receiver<()>) {
+        let mut interval = time::interval(Duration::from_secs(10));
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    self.poll_node_changes().await;
+                }
+                _ = shutdown.changed() => {
+                    info!("node watcher shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn poll_node_changes(&self) {
+        let nodes = match self.list_all_nodes().await {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                warn!("node watcher: error listing nodes: {}", e);
+                return;
+            }
+        };
+
+        let mut snapshot = self.node_snapshot.write().await;
+        let mut seen = HashSet::new();
+        for mut node in nodes {
+            let name = node.metadata.name.clone();
+            seen.insert(name.clone());
+            let signature = NodeSignature::from_node(&node);
+
+            let event_type = match snapshot.get(&name) {
+                None => Some("ADDED"),
+                Some(prev) if *prev != signature => Some("MODIFIED"),
+                _ => None,
+            };
+
+            if let Some(event_type) = event_type {
+                node.metadata.resource_version = self.next_resource_version();
+                let ev = NodeWatchEvent {
+                    event_type: event_type.to_string(),
+                    object: node,
+                };
+                self.record_node_event(&ev).await;
+                let _ = self.node_watch_tx.send(ev);
+            }
+
+            snapshot.insert(name, signature);
+        }
+
+        let removed: Vec<String> = snapshot
+            .keys()
+            .filter(|name| !seen.contains(*name))
+            .cloned()
+            .collect();
+        for name in removed {
+            snapshot.remove(&name);
+            let mut object = Node::default();
+            object.metadata.name = name;
+            object.metadata.resource_version = self.next_resource_version();
+            let ev = NodeWatchEvent {
+                event_type: "DELETED".to_string(),
+                object,
+            };
+            self.record_node_event(&ev).await;
+            let _ = self.node_watch_tx.send(ev);
+        }
+    }
+
     pub async fn get_pod(
         &self,
         ns: &str,
         name: &str,
     ) -> Result<(Pod, String), Box<dyn std::error::Error + Send + Sync>> {
-        let clients = self.snapshot().await;
+        let cached = self
+            .store
+            .all_pods()
+            .await
+            .into_iter()
+            .find(|p| p.metadata.namespace == ns && p.metadata.name == name);
+
+        if let Some(pod) = cached {
+            let node = pod
+                .metadata
+                .annotations
+                .as_ref()
+                .and_then(|a| a.get("mkube.io/node"))
+                .cloned()
+                .unwrap_or_default();
+            return Ok((pod, node));
+        }
 
+        // Cache miss: fall back to a fan-out in case the pod is newer than
+        // the last watch event the store has applied.
+        let clients = self.snapshot().await;
         for client in &clients {
             if let Ok(mut pod) = client.get_pod(ns, name).await {
                 let annotations = pod.metadata.annotations.get_or_insert_with(HashMap::new);
@@ -107,36 +444,95 @@ impl Aggregator {
         &self,
         pod: &Pod,
     ) -> Result<Pod, Box<dyn std::error::Error + Send + Sync>> {
-        let clients_map = self.clients.read().await;
-
-        // Route by nodeName if specified
+        // Explicit nodeName bypasses the scheduler entirely.
         if !pod.spec.node_name.is_empty() {
-            if let Some(c) = clients_map.get(&pod.spec.node_name) {
-                return c.create_pod(pod).await;
-            }
-            return Err(format!("node {:?} not found", pod.spec.node_name).into());
+            let clients_map = self.clients.read().await;
+            let c = clients_map
+                .get(&pod.spec.node_name)
+                .cloned()
+                .ok_or_else(|| format!("node {:?} not found", pod.spec.node_name))?;
+            drop(clients_map);
+            return c.create_pod(pod).await;
         }
 
-        // Least-pods scheduling
-        let mut target: Option<Arc<NodeClient>> = None;
-        let mut min_pods = usize::MAX;
+        let counts = self.store.pod_count_by_node().await;
+        let all_pods = self.store.all_pods().await;
 
-        for c in clients_map.values() {
-            if !c.is_healthy() {
-                continue;
-            }
-            if let Ok(list) = c.list_pods().await {
-                if list.items.len() < min_pods {
-                    min_pods = list.items.len();
-                    target = Some(c.clone());
-                }
-            }
+        // Score every healthy node in parallel rather than one at a time.
+        let healthy: Vec<Arc<NodeClient>> = self
+            .snapshot()
+            .await
+            .into_iter()
+            .filter(|c| c.is_healthy())
+            .collect();
+
+        let mut handles = Vec::new();
+        for c in &healthy {
+            let c = c.clone();
+            handles.push(tokio::spawn(async move {
+                let node = c.get_node().await.ok();
+                (c, node)
+            }));
+        }
+
+        let mut candidates = Vec::new();
+        for handle in handles {
+            let (c, node) = match handle.await {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+            let node = match node {
+                Some(n) => n,
+                None => continue,
+            };
+
+            let (requested_cpu_millis, requested_memory_bytes) = scheduler::sum_requests(
+                all_pods.iter().filter(|p| {
+                    p.metadata
+                        .annotations
+                        .as_ref()
+                        .and_then(|a| a.get("mkube.io/node"))
+                        .map(|n| n == &c.name)
+                        .unwrap_or(false)
+                }),
+            );
+
+            candidates.push(scheduler::Candidate {
+                name: c.name.clone(),
+                labels: node.metadata.labels.clone().unwrap_or_default(),
+                pod_count: counts.get(&c.name).copied().unwrap_or(0),
+                allocatable_cpu_millis: node
+                    .status
+                    .allocatable
+                    .get("cpu")
+                    .map(|v| crate::helpers::parse_cpu_millis(v))
+                    .unwrap_or(0),
+                allocatable_memory_bytes: node
+                    .status
+                    .allocatable
+                    .get("memory")
+                    .map(|v| crate::helpers::parse_memory_bytes(v))
+                    .unwrap_or(0),
+                requested_cpu_millis,
+                requested_memory_bytes,
+            });
         }
 
-        match target {
-            Some(c) => c.create_pod(pod).await,
-            None => Err("no healthy nodes available".into()),
+        if let Some(selector) = &pod.spec.node_selector {
+            candidates.retain(|c| scheduler::matches_selector(c, selector));
         }
+
+        let target_name = self
+            .scheduler
+            .select(&candidates)
+            .map(|c| c.name.clone())
+            .ok_or("no healthy nodes available")?;
+
+        let clients_map = self.clients.read().await;
+        let c = clients_map
+            .get(&target_name)
+            .ok_or_else(|| format!("node {:?} not found", target_name))?;
+        c.create_pod(pod).await
     }
 
     pub async fn delete_pod(
@@ -157,6 +553,7 @@ impl Aggregator {
         &self,
         ns: &str,
         name: &str,
+        opts: &crate::clients::LogOptions,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let (_, node_name) = self.get_pod(ns, name).await?;
 
@@ -164,7 +561,43 @@ impl Aggregator {
         let c = clients_map
             .get(&node_name)
             .ok_or_else(|| format!("node {:?} not found", node_name))?;
-        c.get_pod_log(ns, name).await
+        c.get_pod_log(ns, name, opts).await
+    }
+
+    /// Resolves the node owning `ns/name` and proxies its log stream without
+    /// buffering, so the console can tail long-running pods live.
+    pub async fn stream_pod_log(
+        &self,
+        ns: &str,
+        name: &str,
+        opts: &crate::clients::LogOptions,
+    ) -> Result<LogStream, Box<dyn std::error::Error + Send + Sync>> {
+        let (_, node_name) = self.get_pod(ns, name).await?;
+
+        let clients_map = self.clients.read().await;
+        let c = clients_map
+            .get(&node_name)
+            .ok_or_else(|| format!("node {:?} not found", node_name))?;
+        c.stream_pod_log(ns, name, opts).await
+    }
+
+    /// Resolves the node owning `ns/name` and opens an exec session against
+    /// it so the console can bridge an interactive shell into the pod.
+    pub async fn exec_pod(
+        &self,
+        ns: &str,
+        name: &str,
+        container: &str,
+        command: &[String],
+        tty: bool,
+    ) -> Result<crate::clients::ExecStream, Box<dyn std::error::Error + Send + Sync>> {
+        let (_, node_name) = self.get_pod(ns, name).await?;
+
+        let clients_map = self.clients.read().await;
+        let c = clients_map
+            .get(&node_name)
+            .ok_or_else(|| format!("node {:?} not found", node_name))?;
+        c.exec_pod(ns, name, container, command, tty).await
     }
 
     pub async fn get_node(
@@ -180,6 +613,7 @@ impl Aggregator {
 
     pub async fn get_cluster_summary(&self) -> ClusterSummary {
         let clients = self.snapshot().await;
+        let counts = self.store.pod_count_by_node().await;
 
         let mut summary = ClusterSummary {
             node_count: clients.len(),
@@ -187,28 +621,27 @@ impl Aggregator {
         };
 
         for c in &clients {
-            let mut ns = NodeSummary {
+            let pod_count = counts.get(&c.name).copied().unwrap_or(0);
+            let ns = NodeSummary {
                 name: c.name.clone(),
                 healthy: c.is_healthy(),
-                pod_count: 0,
+                health_state: c.health_state(),
+                consecutive_failures: c.consecutive_failures(),
+                pod_count,
                 last_ping: c.last_ping(),
             };
 
             if c.is_healthy() {
                 summary.healthy_nodes += 1;
             }
+            summary.pod_count += pod_count;
+            summary.nodes.push(ns);
+        }
 
-            if let Ok(list) = c.list_pods().await {
-                ns.pod_count = list.items.len();
-                summary.pod_count += list.items.len();
-                for pod in &list.items {
-                    if pod.status.phase == "Running" {
-                        summary.running_pods += 1;
-                    }
-                }
+        for pod in self.store.all_pods().await {
+            if pod.status.phase == "Running" {
+                summary.running_pods += 1;
             }
-
-            summary.nodes.push(ns);
         }
 
         summary
@@ -216,15 +649,17 @@ impl Aggregator {
 
     pub async fn run_health_checker(self: Arc<Self>, mut shutdown: tokio::sync::watch::Receiver<()>) {
         // Initial check
-        self.ping_all().await;
+        self.ping_due().await;
 
-        let mut interval = time::interval(Duration::from_secs(15));
+        // Tick faster than the normal ping interval so nodes on a probe
+        // backoff get re-checked promptly once their backoff elapses.
+        let mut interval = time::interval(Duration::from_secs(5));
         interval.tick().await; // skip first immediate tick
 
         loop {
             tokio::select! {
                 _ = interval.tick() => {
-                    self.ping_all().await;
+                    self.ping_due().await;
                 }
                 _ = shutdown.changed() => {
                     info!("health checker shutting down");
@@ -234,16 +669,243 @@ impl Aggregator {
         }
     }
 
-    async fn ping_all(&self) {
+    /// Opens a watch against every node and merges their event streams onto
+    /// the shared `pod_watch_tx` broadcast channel. Each node runs its own
+    /// reconnect-with-backoff loop so one node's watch dropping doesn't
+    /// disturb the others.
+    pub async fn run_pod_watcher(self: Arc<Self>, shutdown: tokio::sync::watch::Receiver<()>) {
+        *self.pod_watch_shutdown.write().await = Some(shutdown.clone());
+
         let clients = self.snapshot().await;
+        for c in clients {
+            self.spawn_watch_task(c, shutdown.clone()).await;
+        }
+
+        let agg = self.clone();
+        let bookmark_shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            agg.run_watch_bookmarks(bookmark_shutdown).await;
+        });
+
+        let mut shutdown = shutdown;
+        let _ = shutdown.changed().await;
+        info!("pod watcher shutting down");
+        for (_, h) in self.watch_tasks.write().await.drain() {
+            h.abort();
+        }
+    }
+
+    /// Periodically emits a BOOKMARK carrying the latest resourceVersion on
+    /// both watch channels, so a client reconnecting during a quiet period
+    /// still has a recent version to resume from.
+    async fn run_watch_bookmarks(&self, mut shutdown: tokio::sync::watch::Receiver<()>) {
+        let mut interval = time::interval(WATCH_BOOKMARK_INTERVAL);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let version = self.current_resource_version();
+                    let _ = self.pod_watch_tx.send(PodWatchEvent::version_bookmark(&version));
+                    let _ = self.node_watch_tx.send(NodeWatchEvent::version_bookmark(&version));
+                }
+                _ = shutdown.changed() => return,
+            }
+        }
+    }
+
+    /// Spawns a node's watch-with-backoff task and tracks its handle so it
+    /// can be stopped later if the node leaves membership.
+    async fn spawn_watch_task(
+        self: &Arc<Self>,
+        c: Arc<NodeClient>,
+        shutdown: tokio::sync::watch::Receiver<()>,
+    ) {
+        let agg = self.clone();
+        let name = c.name.clone();
+        let handle = tokio::spawn(async move {
+            agg.watch_node_with_backoff(c, shutdown).await;
+        });
+        self.watch_tasks.write().await.insert(name, handle);
+    }
+
+    async fn watch_node_with_backoff(
+        &self,
+        c: Arc<NodeClient>,
+        mut shutdown: tokio::sync::watch::Receiver<()>,
+    ) {
+        const BASE_BACKOFF: Duration = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+        let mut backoff = BASE_BACKOFF;
+
+        loop {
+            if !c.is_healthy() {
+                tokio::select! {
+                    _ = time::sleep(backoff) => {}
+                    _ = shutdown.changed() => return,
+                }
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                continue;
+            }
+
+            // Reseed this node's slice of the store from a full list before
+            // trusting incremental watch events for it.
+            match c.list_pods().await {
+                Ok(list) => {
+                    let pods: Vec<Pod> = list
+                        .items
+                        .into_iter()
+                        .map(|mut pod| {
+                            let annotations =
+                                pod.metadata.annotations.get_or_insert_with(HashMap::new);
+                            annotations.insert("mkube.io/node".to_string(), c.name.clone());
+                            pod
+                        })
+                        .collect();
+                    self.store.seed_node(&c.name, pods).await;
+                }
+                Err(e) => {
+                    // Leave the store's last-known slice for this node in place:
+                    // it's stale but still more useful to readers than nothing,
+                    // and seed_node will replace it wholesale once this loop
+                    // reconnects.
+                    warn!("failed to seed cluster store from {}: {}", c.name, e);
+                }
+            }
+
+            match c.watch_pods().await {
+                Ok(resp) => {
+                    backoff = BASE_BACKOFF;
+                    // A fresh connection resyncs the receiver's view of this node.
+                    let _ = self.pod_watch_tx.send(PodWatchEvent::bookmark(&c.name));
+                    if let Err(e) = self.consume_watch(&c.name, resp, &mut shutdown).await {
+                        // Same reasoning as above: a transient watch error isn't a
+                        // membership change, so the cached pods stay put until
+                        // the next successful reseed.
+                        warn!("watch stream for {} ended: {}", c.name, e);
+                    }
+                }
+                Err(e) => {
+                    warn!("failed to open watch for {}: {}", c.name, e);
+                }
+            }
+
+            tokio::select! {
+                _ = time::sleep(backoff) => {}
+                _ = shutdown.changed() => return,
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    /// Reads newline-delimited watch events off `resp` and republishes each
+    /// one, tagged with `node_name`, until the stream ends or `shutdown` fires.
+    async fn consume_watch(
+        &self,
+        node_name: &str,
+        resp: reqwest::Response,
+        shutdown: &mut tokio::sync::watch::Receiver<()>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut stream = resp.bytes_stream();
+        let mut buf = String::new();
+
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = shutdown.changed() => return Ok(()),
+            };
+
+            let bytes = match chunk {
+                Some(Ok(b)) => b,
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+            };
+
+            buf.push_str(&String::from_utf8_lossy(&bytes));
+            while let Some(pos) = buf.find('\n') {
+                let line = buf[..pos].trim().to_string();
+                buf.drain(..=pos);
+                if line.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<PodWatchEvent>(&line) {
+                    Ok(mut ev) => {
+                        let annotations = ev.object.metadata.annotations.get_or_insert_with(HashMap::new);
+                        annotations.insert("mkube.io/node".to_string(), node_name.to_string());
+                        ev.object.metadata.resource_version = self.next_resource_version();
+                        self.store.apply_event(&ev).await;
+                        self.record_pod_event(&ev).await;
+                        // No subscribers is fine; the event is simply dropped.
+                        let _ = self.pod_watch_tx.send(ev);
+                    }
+                    Err(e) => warn!("bad watch event from {}: {}", node_name, e),
+                }
+            }
+        }
+    }
+
+    /// Pings only the nodes whose next probe is due, rather than every node
+    /// on one fixed interval, so a backed-off unhealthy node isn't hammered
+    /// and a healthy one isn't probed more often than it needs to be.
+    async fn ping_due(&self) {
+        let clients = self.snapshot().await;
+        let now = Utc::now();
         for c in &clients {
-            if let Err(e) = c.ping().await {
-                warn!("health check failed for {}: {}", c.name, e);
+            if c.probe_due(now) {
+                if let Err(e) = c.ping().await {
+                    warn!("health check failed for {}: {}", c.name, e);
+                }
             }
         }
     }
 
-    async fn snapshot(&self) -> Vec<Arc<NodeClient>> {
+    pub(crate) async fn snapshot(&self) -> Vec<Arc<NodeClient>> {
         self.clients.read().await.values().cloned().collect()
     }
 }
+
+/// The subset of node state that `run_node_watcher` treats as
+/// change-worthy: readiness, pressure conditions, and the uptime/cpu-load
+/// annotations mkube stamps on each node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NodeSignature {
+    ready: bool,
+    memory_pressure: bool,
+    disk_pressure: bool,
+    pid_pressure: bool,
+    uptime: String,
+    cpu_load: String,
+}
+
+impl NodeSignature {
+    fn from_node(node: &Node) -> Self {
+        let mut sig = NodeSignature {
+            ready: false,
+            memory_pressure: false,
+            disk_pressure: false,
+            pid_pressure: false,
+            uptime: String::new(),
+            cpu_load: String::new(),
+        };
+
+        for cond in &node.status.conditions {
+            match cond.condition_type.as_str() {
+                "Ready" => sig.ready = cond.status == "True",
+                "MemoryPressure" => sig.memory_pressure = cond.status == "True",
+                "DiskPressure" => sig.disk_pressure = cond.status == "True",
+                "PIDPressure" => sig.pid_pressure = cond.status == "True",
+                _ => {}
+            }
+        }
+
+        if let Some(ref annotations) = node.metadata.annotations {
+            sig.uptime = annotations.get("mkube.io/uptime").cloned().unwrap_or_default();
+            sig.cpu_load = annotations
+                .get("mkube.io/cpu-load")
+                .cloned()
+                .unwrap_or_default();
+        }
+
+        sig
+    }
+}
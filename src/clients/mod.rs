@@ -1,64 +1,313 @@
 pub mod aggregator;
+pub mod store;
 
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use futures_util::Stream;
 use reqwest::Client;
 use serde::de::DeserializeOwned;
+use std::pin::Pin;
 use std::sync::Mutex;
 use std::time::Duration;
 
+use crate::config::{HealthConfig, SecurityConfig};
 use crate::models::k8s::{Node, Pod, PodList};
 
+/// A live byte stream proxied from a node-agent's log endpoint.
+pub type LogStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+/// A WebSocket connection to a node-agent's `exec` endpoint, carrying
+/// Docker-framed stdout/stderr in and raw stdin out.
+pub type ExecStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Error returned when a node-agent HTTP call responds with a non-2xx
+/// status, carrying that status code so route handlers can map it to the
+/// right K8s `Status` `reason` (`NotFound`, `AlreadyExists`, `Conflict`, ...)
+/// instead of assuming every failure means the same thing.
+#[derive(Debug)]
+pub struct NodeApiError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl std::fmt::Display for NodeApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "status {}: {}", self.status, self.message)
+    }
+}
+
+impl std::error::Error for NodeApiError {}
+
+/// Mirrors the standard Kubernetes `pods/log` query parameters so callers can
+/// request tails, time windows, timestamps, and the previous container's logs
+/// the same way `kubectl logs` does; the node agent honors each of these
+/// itself, so this just forwards them on the query string.
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    pub container: String,
+    pub follow: bool,
+    pub tail_lines: Option<i64>,
+    pub since_seconds: Option<i64>,
+    pub since_time: Option<String>,
+    pub timestamps: bool,
+    pub previous: bool,
+}
+
+impl LogOptions {
+    fn query_string(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.container.is_empty() {
+            parts.push(format!("container={}", self.container));
+        }
+        if self.follow {
+            parts.push("follow=true".to_string());
+        }
+        if let Some(n) = self.tail_lines {
+            parts.push(format!("tailLines={}", n));
+        }
+        if let Some(s) = self.since_seconds {
+            parts.push(format!("sinceSeconds={}", s));
+        }
+        if let Some(t) = &self.since_time {
+            parts.push(format!("sinceTime={}", t));
+        }
+        if self.timestamps {
+            parts.push("timestamps=true".to_string());
+        }
+        if self.previous {
+            parts.push("previous=true".to_string());
+        }
+        parts.join("&")
+    }
+}
+
+/// Base interval between health checks once a node is considered healthy.
+const NORMAL_PING_INTERVAL: Duration = Duration::from_secs(15);
+/// Starting backoff for probing an unhealthy node, doubling up to a cap.
+const BASE_PROBE_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_PROBE_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Hysteresis-filtered view of a node's reachability. A single failed probe
+/// only nudges a `Healthy` node to `Suspect`; it takes a run of consecutive
+/// failures to actually declare it `Unhealthy`, and a run of consecutive
+/// successes (via the half-open `Probing` state) to trust it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+    Healthy,
+    Suspect,
+    Unhealthy,
+    Probing,
+}
+
 pub struct NodeClient {
     pub name: String,
     pub address: String,
     http: Client,
+    health_config: HealthConfig,
+    bearer_token: Option<String>,
+    /// Whether this node came from the static config file rather than a
+    /// runtime `/register` call. Config nodes are exempt from heartbeat-TTL
+    /// membership eviction: they're expected to exist, so a transient outage
+    /// should leave them in place for the health-state machine to recover,
+    /// not drop them from the cluster permanently.
+    static_node: bool,
     state: Mutex<ClientState>,
 }
 
 struct ClientState {
-    healthy: bool,
+    health: HealthState,
+    consecutive_failures: u32,
+    consecutive_successes: u32,
     last_ping: Option<DateTime<Utc>>,
+    next_probe_at: DateTime<Utc>,
+    probe_backoff: Duration,
+    /// Last time this node proved it's still part of the cluster, via either
+    /// a successful health ping or a `/register` call. Distinct from
+    /// `health`: a node can be `Unhealthy` yet still heartbeat-fresh and thus
+    /// stay in membership.
+    last_heartbeat: DateTime<Utc>,
 }
 
 impl NodeClient {
-    pub fn new(name: String, address: String) -> Self {
-        let http = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .expect("failed to create HTTP client");
+    pub fn new(
+        name: String,
+        address: String,
+        health_config: HealthConfig,
+        security: Option<&SecurityConfig>,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::new_inner(name, address, health_config, security, false)
+    }
 
-        Self {
+    /// Builds a client for a node declared in the static config file. Such
+    /// nodes are exempt from heartbeat-TTL membership eviction; see
+    /// [`NodeClient::static_node`]'s doc comment on the struct field.
+    pub fn new_static(
+        name: String,
+        address: String,
+        health_config: HealthConfig,
+        security: Option<&SecurityConfig>,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::new_inner(name, address, health_config, security, true)
+    }
+
+    fn new_inner(
+        name: String,
+        address: String,
+        health_config: HealthConfig,
+        security: Option<&SecurityConfig>,
+        static_node: bool,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut builder = Client::builder().timeout(Duration::from_secs(10));
+        let mut bearer_token = None;
+
+        if let Some(security) = security {
+            if let Some(ca_path) = &security.ca_cert_path {
+                let pem = std::fs::read(ca_path)
+                    .map_err(|e| format!("reading CA cert {}: {}", ca_path, e))?;
+                builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+            }
+
+            if let (Some(cert_path), Some(key_path)) =
+                (&security.client_cert_path, &security.client_key_path)
+            {
+                let mut identity_pem = std::fs::read(cert_path)
+                    .map_err(|e| format!("reading client cert {}: {}", cert_path, e))?;
+                let mut key_pem = std::fs::read(key_path)
+                    .map_err(|e| format!("reading client key {}: {}", key_path, e))?;
+                identity_pem.append(&mut key_pem);
+                builder = builder.identity(reqwest::Identity::from_pem(&identity_pem)?);
+            }
+
+            bearer_token = security.bearer_token.clone();
+        }
+
+        let http = builder.build()?;
+
+        Ok(Self {
             name,
             address,
             http,
+            health_config,
+            bearer_token,
+            static_node,
             state: Mutex::new(ClientState {
-                healthy: true,
+                health: HealthState::Healthy,
+                consecutive_failures: 0,
+                consecutive_successes: 0,
                 last_ping: None,
+                next_probe_at: Utc::now(),
+                probe_backoff: BASE_PROBE_BACKOFF,
+                last_heartbeat: Utc::now(),
             }),
+        })
+    }
+
+    /// Applies the configured bearer token, if any, to an outgoing request.
+    fn authorize(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.bearer_token {
+            Some(token) => req.bearer_auth(token),
+            None => req,
         }
     }
 
+    /// Refreshes this node's heartbeat, e.g. on a `/register` call that
+    /// re-registers an already-known node.
+    pub fn touch_heartbeat(&self) {
+        self.state.lock().unwrap().last_heartbeat = Utc::now();
+    }
+
+    pub fn last_heartbeat(&self) -> DateTime<Utc> {
+        self.state.lock().unwrap().last_heartbeat
+    }
+
+    /// Whether this node was declared in the static config file. Such nodes
+    /// are exempt from heartbeat-TTL membership eviction.
+    pub fn is_static(&self) -> bool {
+        self.static_node
+    }
+
+    /// Whether this node's next scheduled probe is due, so the checker only
+    /// wakes nodes that are actually ready to be re-pinged instead of
+    /// hitting everyone on one fixed interval.
+    pub fn probe_due(&self, now: DateTime<Utc>) -> bool {
+        self.state.lock().unwrap().next_probe_at <= now
+    }
+
     pub async fn ping(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let resp = self
-            .http
-            .get(format!("{}/healthz", self.address))
+        let result = self
+            .authorize(self.http.get(format!("{}/healthz", self.address)))
             .send()
-            .await?;
+            .await;
+        let success = matches!(&result, Ok(resp) if resp.status().is_success());
+
+        let mut state = self.state.lock().unwrap();
+        state.last_ping = Some(Utc::now());
 
-        if resp.status().is_success() {
-            let mut state = self.state.lock().unwrap();
-            state.healthy = true;
-            state.last_ping = Some(Utc::now());
+        if success {
+            state.consecutive_failures = 0;
+            state.consecutive_successes += 1;
+            state.last_heartbeat = Utc::now();
+
+            state.health = match state.health {
+                HealthState::Healthy => HealthState::Healthy,
+                HealthState::Suspect => HealthState::Healthy,
+                HealthState::Unhealthy | HealthState::Probing => {
+                    if state.consecutive_successes >= self.health_config.success_threshold {
+                        state.probe_backoff = BASE_PROBE_BACKOFF;
+                        HealthState::Healthy
+                    } else {
+                        HealthState::Probing
+                    }
+                }
+            };
+
+            state.next_probe_at = Utc::now() + NORMAL_PING_INTERVAL;
             Ok(())
         } else {
-            let mut state = self.state.lock().unwrap();
-            state.healthy = false;
-            Err(format!("node {} health check returned {}", self.name, resp.status()).into())
+            state.consecutive_successes = 0;
+            state.consecutive_failures += 1;
+
+            state.health = match state.health {
+                HealthState::Healthy | HealthState::Suspect => {
+                    if state.consecutive_failures >= self.health_config.failure_threshold {
+                        HealthState::Unhealthy
+                    } else {
+                        HealthState::Suspect
+                    }
+                }
+                HealthState::Unhealthy | HealthState::Probing => {
+                    state.probe_backoff = (state.probe_backoff * 2).min(MAX_PROBE_BACKOFF);
+                    HealthState::Unhealthy
+                }
+            };
+
+            state.next_probe_at = Utc::now() + jitter(state.probe_backoff);
+
+            let detail = match &result {
+                Ok(resp) => format!("status {}", resp.status()),
+                Err(e) => e.to_string(),
+            };
+            Err(format!("node {} health check failed: {}", self.name, detail).into())
         }
     }
 
+    /// Treats `Suspect` as still usable (it hasn't crossed the failure
+    /// threshold yet); only `Unhealthy`/`Probing` nodes are excluded from
+    /// scheduling and from opening a new pod watch.
     pub fn is_healthy(&self) -> bool {
-        self.state.lock().unwrap().healthy
+        matches!(
+            self.state.lock().unwrap().health,
+            HealthState::Healthy | HealthState::Suspect
+        )
+    }
+
+    pub fn health_state(&self) -> HealthState {
+        self.state.lock().unwrap().health
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
     }
 
     pub fn last_ping(&self) -> Option<DateTime<Utc>> {
@@ -95,17 +344,17 @@ impl NodeClient {
         name: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let resp = self
-            .http
-            .delete(format!(
+            .authorize(self.http.delete(format!(
                 "{}/api/v1/namespaces/{}/pods/{}",
                 self.address, ns, name
-            ))
+            )))
             .send()
             .await?;
 
         if resp.status().as_u16() >= 400 {
-            let body = resp.text().await.unwrap_or_default();
-            return Err(format!("delete pod failed: {}", body).into());
+            let status = resp.status().as_u16();
+            let message = resp.text().await.unwrap_or_default();
+            return Err(Box::new(NodeApiError { status, message }));
         }
         Ok(())
     }
@@ -114,64 +363,128 @@ impl NodeClient {
         &self,
         ns: &str,
         name: &str,
+        opts: &LogOptions,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let resp = self
-            .http
-            .get(format!(
-                "{}/api/v1/namespaces/{}/pods/{}/log",
-                self.address, ns, name
-            ))
+            .authorize(self.http.get(format!(
+                "{}/api/v1/namespaces/{}/pods/{}/log?{}",
+                self.address,
+                ns,
+                name,
+                opts.query_string()
+            )))
             .send()
             .await?;
 
         if resp.status().as_u16() >= 400 {
-            let body = resp.text().await.unwrap_or_default();
-            return Err(format!("get pod log failed: {}", body).into());
+            let status = resp.status().as_u16();
+            let message = resp.text().await.unwrap_or_default();
+            return Err(Box::new(NodeApiError { status, message }));
         }
         Ok(resp.text().await?)
     }
 
-    pub async fn get_node(&self) -> Result<Node, Box<dyn std::error::Error + Send + Sync>> {
-        self.get_json(&format!("/api/v1/nodes/{}", self.name)).await
-    }
-
-    pub async fn watch_pods(
+    /// Opens the pod's log endpoint and returns the raw byte stream rather
+    /// than buffering the whole body, so callers can tail logs from
+    /// long-running or noisy pods without holding everything in memory.
+    /// Intended for `opts.follow == true`, but works either way.
+    pub async fn stream_pod_log(
         &self,
-    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        ns: &str,
+        name: &str,
+        opts: &LogOptions,
+    ) -> Result<LogStream, Box<dyn std::error::Error + Send + Sync>> {
         let resp = self
-            .http
-            .get(format!("{}/api/v1/pods?watch=true", self.address))
-            .header("Accept", "application/json")
+            .authorize(self.http.get(format!(
+                "{}/api/v1/namespaces/{}/pods/{}/log?{}",
+                self.address,
+                ns,
+                name,
+                opts.query_string()
+            )))
             .send()
             .await?;
 
         if resp.status().as_u16() >= 400 {
-            let body = resp.text().await.unwrap_or_default();
-            return Err(format!("watch pods failed: {}", body).into());
+            let status = resp.status().as_u16();
+            let message = resp.text().await.unwrap_or_default();
+            return Err(Box::new(NodeApiError { status, message }));
         }
-        Ok(resp)
+        Ok(Box::pin(resp.bytes_stream()))
     }
 
-    pub async fn get_container_log(
+    pub async fn get_node(&self) -> Result<Node, Box<dyn std::error::Error + Send + Sync>> {
+        self.get_json(&format!("/api/v1/nodes/{}", self.name)).await
+    }
+
+    /// Fetches this node's cumulative CPU/memory counters for itself and
+    /// every pod it hosts, for the `metrics.k8s.io` rate computation.
+    pub async fn get_stats(
+        &self,
+    ) -> Result<crate::metrics::NodeStats, Box<dyn std::error::Error + Send + Sync>> {
+        self.get_json("/api/v1/stats").await
+    }
+
+    /// Opens a `v4.channel.k8s.io`-style exec session against this node's
+    /// `pods/exec` subresource. The caller is responsible for the channel-byte
+    /// framing and Docker stream demuxing; this just establishes the socket.
+    pub async fn exec_pod(
         &self,
         ns: &str,
-        pod_name: &str,
-        container_name: &str,
-    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        name: &str,
+        container: &str,
+        command: &[String],
+        tty: bool,
+    ) -> Result<ExecStream, Box<dyn std::error::Error + Send + Sync>> {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+        use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+
+        let mut url = reqwest::Url::parse(&format!(
+            "{}/api/v1/namespaces/{}/pods/{}/exec",
+            self.address, ns, name
+        ))?;
+        url.set_scheme(if url.scheme() == "https" { "wss" } else { "ws" })
+            .map_err(|_| "failed to rewrite exec URL scheme")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("tty", &tty.to_string());
+            if !container.is_empty() {
+                pairs.append_pair("container", container);
+            }
+            for arg in command {
+                pairs.append_pair("command", arg);
+            }
+        }
+
+        let mut request = url.as_str().into_client_request()?;
+        if let Some(token) = &self.bearer_token {
+            request.headers_mut().insert(
+                AUTHORIZATION,
+                format!("Bearer {}", token).parse()?,
+            );
+        }
+
+        let (ws_stream, _) = tokio_tungstenite::connect_async(request).await?;
+        Ok(ws_stream)
+    }
+
+    pub async fn watch_pods(
+        &self,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
         let resp = self
-            .http
-            .get(format!(
-                "{}/api/v1/namespaces/{}/pods/{}/log?container={}",
-                self.address, ns, pod_name, container_name
-            ))
+            .authorize(
+                self.http
+                    .get(format!("{}/api/v1/pods?watch=true", self.address))
+                    .header("Accept", "application/json"),
+            )
             .send()
             .await?;
 
         if resp.status().as_u16() >= 400 {
             let body = resp.text().await.unwrap_or_default();
-            return Err(format!("get container log failed: {}", body).into());
+            return Err(format!("watch pods failed: {}", body).into());
         }
-        Ok(resp.text().await?)
+        Ok(resp)
     }
 
     async fn get_json<T: DeserializeOwned>(
@@ -179,15 +492,18 @@ impl NodeClient {
         path: &str,
     ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
         let resp = self
-            .http
-            .get(format!("{}{}", self.address, path))
-            .header("Accept", "application/json")
+            .authorize(
+                self.http
+                    .get(format!("{}{}", self.address, path))
+                    .header("Accept", "application/json"),
+            )
             .send()
             .await?;
 
         if resp.status().as_u16() >= 400 {
-            let body = resp.text().await.unwrap_or_default();
-            return Err(format!("GET {} returned error: {}", path, body).into());
+            let status = resp.status().as_u16();
+            let message = resp.text().await.unwrap_or_default();
+            return Err(Box::new(NodeApiError { status, message }));
         }
         Ok(resp.json().await?)
     }
@@ -198,18 +514,31 @@ impl NodeClient {
         body: &impl serde::Serialize,
     ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
         let resp = self
-            .http
-            .post(format!("{}{}", self.address, path))
-            .header("Content-Type", "application/json")
-            .header("Accept", "application/json")
-            .json(body)
+            .authorize(
+                self.http
+                    .post(format!("{}{}", self.address, path))
+                    .header("Content-Type", "application/json")
+                    .header("Accept", "application/json")
+                    .json(body),
+            )
             .send()
             .await?;
 
         if resp.status().as_u16() >= 400 {
-            let body = resp.text().await.unwrap_or_default();
-            return Err(format!("POST {} returned error: {}", path, body).into());
+            let status = resp.status().as_u16();
+            let message = resp.text().await.unwrap_or_default();
+            return Err(Box::new(NodeApiError { status, message }));
         }
         Ok(resp.json().await?)
     }
 }
+
+/// Adds up to ~500ms of jitter to a backoff so a fleet of flapping nodes
+/// doesn't all re-probe in lockstep.
+fn jitter(base: Duration) -> Duration {
+    let subsec_nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    base + Duration::from_millis((subsec_nanos % 500) as u64)
+}
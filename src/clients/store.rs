@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use crate::models::k8s::{Pod, PodWatchEvent};
+
+/// Background-maintained cache of each node's pod list. Seeded once per node
+/// from a full `list_pods` call, then kept current by consuming that node's
+/// watch stream, so read endpoints and the scheduler can answer from memory
+/// instead of fanning a fresh HTTP request out to every node on every request.
+#[derive(Default)]
+pub struct ClusterStore {
+    pods_by_node: RwLock<HashMap<String, Vec<Pod>>>,
+}
+
+impl ClusterStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces a node's cached pod list wholesale. Used for the initial
+    /// seed when a watch connection is (re-)established.
+    pub async fn seed_node(&self, node: &str, pods: Vec<Pod>) {
+        self.pods_by_node.write().await.insert(node.to_string(), pods);
+    }
+
+    /// Drops a node's cached pods entirely. Called when that node actually
+    /// leaves cluster membership, not on a transient watch-connection error —
+    /// a stale-but-present slice is more useful to readers than a gap, and
+    /// `seed_node` replaces it wholesale once the node's watch loop
+    /// reconnects.
+    pub async fn invalidate_node(&self, node: &str) {
+        self.pods_by_node.write().await.remove(node);
+    }
+
+    /// Applies a single watch event (keyed by the `mkube.io/node` annotation)
+    /// to the cache, so the store stays current without a re-list.
+    pub async fn apply_event(&self, event: &PodWatchEvent) {
+        if event.event_type == "BOOKMARK" {
+            return;
+        }
+        let node = match event
+            .object
+            .metadata
+            .annotations
+            .as_ref()
+            .and_then(|a| a.get("mkube.io/node"))
+        {
+            Some(n) => n.clone(),
+            None => return,
+        };
+
+        let mut store = self.pods_by_node.write().await;
+        let pods = store.entry(node).or_default();
+        pods.retain(|p| {
+            p.metadata.namespace != event.object.metadata.namespace
+                || p.metadata.name != event.object.metadata.name
+        });
+        if event.event_type != "DELETED" {
+            pods.push(event.object.clone());
+        }
+    }
+
+    /// Current pod count per node, as held in the cache right now.
+    pub async fn pod_count_by_node(&self) -> HashMap<String, usize> {
+        self.pods_by_node
+            .read()
+            .await
+            .iter()
+            .map(|(node, pods)| (node.clone(), pods.len()))
+            .collect()
+    }
+
+    /// All cached pods across every node.
+    pub async fn all_pods(&self) -> Vec<Pod> {
+        self.pods_by_node
+            .read()
+            .await
+            .values()
+            .flat_map(|pods| pods.iter().cloned())
+            .collect()
+    }
+}
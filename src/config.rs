@@ -19,12 +19,153 @@ pub struct Config {
     pub logs_url: Option<String>,
     #[serde(default)]
     pub networks: Vec<NetworkDef>,
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    #[serde(default)]
+    pub membership: MembershipConfig,
+    #[serde(default)]
+    pub security: Option<SecurityConfig>,
+    #[serde(default)]
+    pub pod_health: PodHealthConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PodHealthConfig {
+    /// Restart count, inclusive, at which a container is flagged as
+    /// crash-looping in the suspicious-pod diagnostics.
+    #[serde(default = "default_restart_threshold")]
+    pub restart_threshold: i32,
+}
+
+impl Default for PodHealthConfig {
+    fn default() -> Self {
+        Self {
+            restart_threshold: default_restart_threshold(),
+        }
+    }
+}
+
+fn default_restart_threshold() -> i32 {
+    5
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityConfig {
+    /// PEM-encoded CA certificate used to verify node-agent TLS certs.
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// PEM-encoded client certificate for mutual TLS against node agents.
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    /// PEM-encoded private key matching `client_cert_path`.
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// Bearer token sent as `Authorization: Bearer <token>` on every request
+    /// to a node agent, in addition to or instead of mTLS.
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MembershipConfig {
+    /// How long a node can go without a heartbeat (a health ping or a
+    /// `/register` call) before it's evicted from the live node set. Applies
+    /// only to nodes registered at runtime; nodes listed in `nodes` are never
+    /// evicted.
+    #[serde(default = "default_heartbeat_ttl_secs")]
+    pub heartbeat_ttl_secs: u64,
+}
+
+impl Default for MembershipConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_ttl_secs: default_heartbeat_ttl_secs(),
+        }
+    }
+}
+
+fn default_heartbeat_ttl_secs() -> u64 {
+    90
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthConfig {
+    /// Consecutive failed pings before a node flips `Healthy` -> `Unhealthy`.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Consecutive successful probes before a recovering node flips back to
+    /// `Healthy`.
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u32,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            success_threshold: default_success_threshold(),
+        }
+    }
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_success_threshold() -> u32 {
+    2
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchedulerConfig {
+    /// One of "least-pods" (default), "round-robin", or "least-allocated".
+    #[serde(default = "default_scheduler_strategy")]
+    pub strategy: String,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            strategy: default_scheduler_strategy(),
+        }
+    }
+}
+
+fn default_scheduler_strategy() -> String {
+    "least-pods".to_string()
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct NodeDef {
     pub name: String,
     pub address: String,
+    /// Per-node override of the top-level `security` block, for fleets where
+    /// agents don't share a single CA, client cert, or token. A field left
+    /// unset here falls back to the corresponding field on `Config::security`,
+    /// not to the whole block at once, so a node can e.g. keep the global CA
+    /// while carrying its own bearer token.
+    #[serde(default)]
+    pub security: Option<SecurityConfig>,
+}
+
+impl NodeDef {
+    /// Resolves this node's effective security config by overlaying its own
+    /// `security` block, field by field, onto the cluster-wide default.
+    pub fn effective_security(&self, default: Option<&SecurityConfig>) -> Option<SecurityConfig> {
+        match (&self.security, default) {
+            (None, None) => None,
+            (None, Some(d)) => Some(d.clone()),
+            (Some(n), None) => Some(n.clone()),
+            (Some(n), Some(d)) => Some(SecurityConfig {
+                ca_cert_path: n.ca_cert_path.clone().or_else(|| d.ca_cert_path.clone()),
+                client_cert_path: n.client_cert_path.clone().or_else(|| d.client_cert_path.clone()),
+                client_key_path: n.client_key_path.clone().or_else(|| d.client_key_path.clone()),
+                bearer_token: n.bearer_token.clone().or_else(|| d.bearer_token.clone()),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -71,6 +71,39 @@ pub fn human_duration_secs(total_secs: i64) -> String {
     }
 }
 
+/// Parses a Kubernetes CPU quantity ("500m", "2") into millicores.
+pub fn parse_cpu_millis(q: &str) -> i64 {
+    if let Some(millis) = q.strip_suffix('m') {
+        millis.parse().unwrap_or(0)
+    } else {
+        q.parse::<f64>().map(|cores| (cores * 1000.0) as i64).unwrap_or(0)
+    }
+}
+
+/// Parses a Kubernetes memory quantity ("512Mi", "1Gi", "2048") into bytes.
+pub fn parse_memory_bytes(q: &str) -> i64 {
+    const UNITS: &[(&str, i64)] = &[
+        ("Ki", 1024),
+        ("Mi", 1024 * 1024),
+        ("Gi", 1024 * 1024 * 1024),
+        ("Ti", 1024 * 1024 * 1024 * 1024),
+        ("K", 1_000),
+        ("M", 1_000_000),
+        ("G", 1_000_000_000),
+    ];
+
+    for (suffix, multiplier) in UNITS {
+        if let Some(value) = q.strip_suffix(suffix) {
+            return value
+                .parse::<f64>()
+                .map(|v| (v * *multiplier as f64) as i64)
+                .unwrap_or(0);
+        }
+    }
+
+    q.parse().unwrap_or(0)
+}
+
 pub fn parse_age(start_time: &Option<String>) -> String {
     let ts = match start_time {
         Some(s) if !s.is_empty() => s,
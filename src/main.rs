@@ -1,8 +1,12 @@
 mod clients;
 mod config;
 mod helpers;
+mod metrics;
 mod models;
+mod registry;
 mod routes;
+mod scheduler;
+mod selectors;
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -13,11 +17,15 @@ use tracing::info;
 
 use clients::aggregator::Aggregator;
 use clients::NodeClient;
+use metrics::MetricsCollector;
+use registry::RegistryCache;
 
 #[derive(Clone)]
 pub struct AppState {
     pub aggregator: Arc<Aggregator>,
     pub config: Arc<config::Config>,
+    pub registry_cache: Arc<RegistryCache>,
+    pub metrics: Arc<MetricsCollector>,
 }
 
 #[tokio::main]
@@ -49,7 +57,18 @@ async fn main() {
 
     let mut node_clients = Vec::new();
     for n in &cfg.nodes {
-        node_clients.push(NodeClient::new(n.name.clone(), n.address.clone()));
+        let security = n.effective_security(cfg.security.as_ref());
+        let client = NodeClient::new_static(
+            n.name.clone(),
+            n.address.clone(),
+            cfg.health.clone(),
+            security.as_ref(),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("error building client for node {}: {}", n.name, e);
+            std::process::exit(1);
+        });
+        node_clients.push(client);
     }
 
     if node_clients.is_empty() {
@@ -57,7 +76,12 @@ async fn main() {
         std::process::exit(1);
     }
 
-    let aggregator = Arc::new(Aggregator::new(node_clients));
+    let aggregator = Arc::new(Aggregator::new(
+        node_clients,
+        &cfg.scheduler.strategy,
+        cfg.health.clone(),
+        cfg.security.clone(),
+    ));
     let cfg = Arc::new(cfg);
 
     // Shutdown signal
@@ -65,13 +89,38 @@ async fn main() {
 
     // Start health checker
     let agg_clone = aggregator.clone();
+    let health_shutdown_rx = shutdown_rx.clone();
+    tokio::spawn(async move {
+        agg_clone.run_health_checker(health_shutdown_rx).await;
+    });
+
+    // Start the merged multi-node pod watcher feeding /ui/events/pods
+    let agg_clone = aggregator.clone();
+    let watcher_shutdown_rx = shutdown_rx.clone();
+    tokio::spawn(async move {
+        agg_clone.run_pod_watcher(watcher_shutdown_rx).await;
+    });
+
+    // Start the node watcher feeding /ui/events/nodes
+    let agg_clone = aggregator.clone();
+    let node_watcher_shutdown_rx = shutdown_rx.clone();
+    tokio::spawn(async move {
+        agg_clone.run_node_watcher(node_watcher_shutdown_rx).await;
+    });
+
+    // Evict nodes that stop heartbeating, independent of the health-flag
+    // mechanism above.
+    let agg_clone = aggregator.clone();
+    let heartbeat_ttl = std::time::Duration::from_secs(cfg.membership.heartbeat_ttl_secs);
     tokio::spawn(async move {
-        agg_clone.run_health_checker(shutdown_rx).await;
+        agg_clone.run_membership_evictor(heartbeat_ttl, shutdown_rx).await;
     });
 
     let state = AppState {
         aggregator,
         config: cfg.clone(),
+        registry_cache: Arc::new(RegistryCache::new()),
+        metrics: Arc::new(MetricsCollector::new()),
     };
 
     let router = routes::build_router(state);
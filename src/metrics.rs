@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::clients::aggregator::Aggregator;
+use crate::models::k8s::{
+    ContainerMetrics, NodeMetrics, NodeMetricsList, ObjectMeta, PodMetrics, PodMetricsList,
+    TypeMeta,
+};
+
+/// The `window` reported on every metrics item. The CPU rate itself is
+/// averaged over the time since the previous request sampled the same node
+/// or container, which is not fixed to this value.
+const METRICS_WINDOW: &str = "30s";
+
+/// Raw cumulative stats reported by a node agent, mirroring the
+/// cAdvisor/kubelet summary shape: counters that must be diffed against a
+/// previous sample to produce a rate.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStats {
+    #[serde(default)]
+    pub cpu_usage_nanos: u64,
+    #[serde(default)]
+    pub memory_usage_bytes: u64,
+    #[serde(default)]
+    pub pods: Vec<PodStats>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodStats {
+    #[serde(default)]
+    pub namespace: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub containers: Vec<ContainerStats>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerStats {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub cpu_usage_nanos: u64,
+    #[serde(default)]
+    pub memory_usage_bytes: u64,
+}
+
+#[derive(Clone, Copy)]
+struct CpuSample {
+    at: DateTime<Utc>,
+    cpu_usage_nanos: u64,
+}
+
+/// Polls every node's cumulative CPU/memory counters and turns them into
+/// `metrics.k8s.io`-shaped rates, keyed by the previous sample so
+/// `kubectl top` sees a millicore rate rather than a raw counter.
+pub struct MetricsCollector {
+    previous: RwLock<HashMap<String, CpuSample>>,
+}
+
+impl MetricsCollector {
+    pub fn new() -> Self {
+        Self {
+            previous: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn node_metrics(&self, aggregator: &Aggregator) -> NodeMetricsList {
+        let clients = aggregator.snapshot().await;
+        let now = Utc::now();
+        let mut items = Vec::new();
+
+        for c in &clients {
+            let stats = match c.get_stats().await {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+
+            let cpu_millis = self
+                .cpu_rate_millis(&format!("node/{}", c.name), stats.cpu_usage_nanos, now)
+                .await;
+
+            items.push(NodeMetrics {
+                type_meta: TypeMeta {
+                    api_version: "metrics.k8s.io/v1beta1".to_string(),
+                    kind: "NodeMetrics".to_string(),
+                },
+                metadata: ObjectMeta {
+                    name: c.name.clone(),
+                    ..Default::default()
+                },
+                timestamp: now.to_rfc3339(),
+                window: METRICS_WINDOW.to_string(),
+                usage: usage_map(cpu_millis, stats.memory_usage_bytes),
+            });
+        }
+
+        NodeMetricsList {
+            type_meta: TypeMeta {
+                api_version: "metrics.k8s.io/v1beta1".to_string(),
+                kind: "NodeMetricsList".to_string(),
+            },
+            items,
+        }
+    }
+
+    pub async fn pod_metrics(&self, aggregator: &Aggregator, namespace: Option<&str>) -> PodMetricsList {
+        let clients = aggregator.snapshot().await;
+        let now = Utc::now();
+        let mut items = Vec::new();
+
+        for c in &clients {
+            let stats = match c.get_stats().await {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+
+            for pod in stats.pods {
+                if let Some(ns) = namespace {
+                    if pod.namespace != ns {
+                        continue;
+                    }
+                }
+
+                let mut containers = Vec::new();
+                for container in &pod.containers {
+                    let key = format!(
+                        "container/{}/{}/{}",
+                        pod.namespace, pod.name, container.name
+                    );
+                    let cpu_millis = self
+                        .cpu_rate_millis(&key, container.cpu_usage_nanos, now)
+                        .await;
+                    containers.push(ContainerMetrics {
+                        name: container.name.clone(),
+                        usage: usage_map(cpu_millis, container.memory_usage_bytes),
+                    });
+                }
+
+                items.push(PodMetrics {
+                    type_meta: TypeMeta {
+                        api_version: "metrics.k8s.io/v1beta1".to_string(),
+                        kind: "PodMetrics".to_string(),
+                    },
+                    metadata: ObjectMeta {
+                        name: pod.name.clone(),
+                        namespace: pod.namespace.clone(),
+                        ..Default::default()
+                    },
+                    timestamp: now.to_rfc3339(),
+                    window: METRICS_WINDOW.to_string(),
+                    containers,
+                });
+            }
+        }
+
+        PodMetricsList {
+            type_meta: TypeMeta {
+                api_version: "metrics.k8s.io/v1beta1".to_string(),
+                kind: "PodMetricsList".to_string(),
+            },
+            items,
+        }
+    }
+
+    /// Diffs a cumulative CPU-nanosecond counter against the last sample for
+    /// `key` to derive a millicore rate over the elapsed wall-clock window.
+    async fn cpu_rate_millis(&self, key: &str, cpu_usage_nanos: u64, now: DateTime<Utc>) -> u64 {
+        let mut previous = self.previous.write().await;
+
+        let rate = match previous.get(key) {
+            Some(prev) if cpu_usage_nanos >= prev.cpu_usage_nanos => {
+                let elapsed_secs = (now - prev.at).num_milliseconds() as f64 / 1000.0;
+                if elapsed_secs > 0.0 {
+                    let delta_nanos = (cpu_usage_nanos - prev.cpu_usage_nanos) as f64;
+                    // 1 millicore is 1e6ns of CPU time consumed per second.
+                    (delta_nanos / elapsed_secs / 1_000_000.0) as u64
+                } else {
+                    0
+                }
+            }
+            _ => 0,
+        };
+
+        previous.insert(key.to_string(), CpuSample { at: now, cpu_usage_nanos });
+        rate
+    }
+}
+
+fn usage_map(cpu_millis: u64, memory_bytes: u64) -> HashMap<String, String> {
+    HashMap::from([
+        ("cpu".to_string(), format!("{}m", cpu_millis)),
+        ("memory".to_string(), format!("{}Ki", memory_bytes / 1024)),
+    ])
+}
@@ -25,6 +25,11 @@ pub struct ObjectMeta {
     pub annotations: Option<HashMap<String, String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub creation_timestamp: Option<String>,
+    /// Monotonically increasing per-aggregator counter, stamped on every
+    /// watch event so clients can resume a dropped watch with
+    /// `?resourceVersion=`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub resource_version: String,
 }
 
 // --- Pod ---
@@ -47,10 +52,35 @@ pub struct Pod {
 pub struct PodSpec {
     #[serde(default)]
     pub node_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_selector: Option<HashMap<String, String>>,
     #[serde(default)]
     pub containers: Vec<Container>,
     #[serde(default)]
     pub volumes: Vec<Volume>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub restart_policy: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub service_account_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub termination_grace_period_seconds: Option<i64>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub dns_policy: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_context: Option<PodSecurityContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodSecurityContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_as_user: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_as_group: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_as_non_root: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fs_group: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -62,6 +92,52 @@ pub struct Container {
     pub image: String,
     #[serde(default)]
     pub volume_mounts: Vec<VolumeMount>,
+    #[serde(default, skip_serializing_if = "ResourceRequirements::is_empty")]
+    pub resources: ResourceRequirements,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<EnvVar>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<ContainerPort>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub image_pull_policy: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVar {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPort {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub container_port: i32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub protocol: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceRequirements {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub requests: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub limits: HashMap<String, String>,
+}
+
+impl ResourceRequirements {
+    fn is_empty(&self) -> bool {
+        self.requests.is_empty() && self.limits.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -103,7 +179,11 @@ pub struct ContainerStatus {
     #[serde(default)]
     pub ready: bool,
     #[serde(default)]
+    pub restart_count: i32,
+    #[serde(default)]
     pub state: ContainerState,
+    #[serde(default)]
+    pub last_state: ContainerState,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -159,6 +239,45 @@ impl Default for PodList {
     }
 }
 
+/// A single entry from a Kubernetes-style `watch=true` stream, as emitted by
+/// node agents and relayed by the aggregator's merged pod watch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodWatchEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub object: Pod,
+}
+
+impl PodWatchEvent {
+    /// A synthetic marker event emitted whenever a node's watch connection is
+    /// (re-)established, so subscribers know to reconcile their view of that
+    /// node instead of assuming they've seen every change since the last one.
+    pub fn bookmark(node_name: &str) -> Self {
+        let mut pod = Pod::default();
+        pod.metadata
+            .annotations
+            .get_or_insert_with(HashMap::new)
+            .insert("mkube.io/node".to_string(), node_name.to_string());
+        Self {
+            event_type: "BOOKMARK".to_string(),
+            object: pod,
+        }
+    }
+
+    /// A periodic marker carrying the latest `resourceVersion`, so a client
+    /// that reconnects with `?resourceVersion=` can skip everything it's
+    /// already seen instead of replaying the whole list.
+    pub fn version_bookmark(resource_version: &str) -> Self {
+        let mut pod = Pod::default();
+        pod.metadata.resource_version = resource_version.to_string();
+        Self {
+            event_type: "BOOKMARK".to_string(),
+            object: pod,
+        }
+    }
+}
+
 // --- Node ---
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -192,6 +311,10 @@ pub struct NodeCondition {
     pub condition_type: String,
     #[serde(default)]
     pub status: String,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub message: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -223,6 +346,30 @@ impl Default for NodeList {
     }
 }
 
+/// A single entry from the node-events watch feed, emitted whenever a node's
+/// readiness, pressure conditions, or uptime/cpu-load annotations change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeWatchEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub object: Node,
+}
+
+impl NodeWatchEvent {
+    /// A periodic marker carrying the latest `resourceVersion`, so a client
+    /// that reconnects with `?resourceVersion=` can skip everything it's
+    /// already seen instead of replaying the whole list.
+    pub fn version_bookmark(resource_version: &str) -> Self {
+        let mut node = Node::default();
+        node.metadata.resource_version = resource_version.to_string();
+        Self {
+            event_type: "BOOKMARK".to_string(),
+            object: node,
+        }
+    }
+}
+
 // --- API Discovery ---
 
 #[derive(Debug, Serialize)]
@@ -264,4 +411,86 @@ pub struct Status {
     pub kind: String,
     pub status: String,
     pub message: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<StatusDetails>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusDetails {
+    pub name: String,
+    pub kind: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiGroupList {
+    pub kind: String,
+    pub groups: Vec<ApiGroup>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiGroup {
+    pub name: String,
+    pub versions: Vec<GroupVersionForDiscovery>,
+    pub preferred_version: GroupVersionForDiscovery,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupVersionForDiscovery {
+    pub group_version: String,
+    pub version: String,
+}
+
+// --- Metrics (metrics.k8s.io/v1beta1) ---
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeMetrics {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub metadata: ObjectMeta,
+    pub timestamp: String,
+    pub window: String,
+    pub usage: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeMetricsList {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub items: Vec<NodeMetrics>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerMetrics {
+    pub name: String,
+    pub usage: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodMetrics {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub metadata: ObjectMeta,
+    pub timestamp: String,
+    pub window: String,
+    pub containers: Vec<ContainerMetrics>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodMetricsList {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub items: Vec<PodMetrics>,
 }
@@ -0,0 +1,3 @@
+pub mod k8s;
+pub mod pod_health;
+pub mod views;
@@ -0,0 +1,90 @@
+use super::k8s::{ContainerStatus, Pod};
+
+/// Heuristic classification of why a container looks unhealthy, mirroring the
+/// usual "suspicious pod" triage signals instead of just relaying the raw
+/// phase/state to the operator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuspiciousReason {
+    ContainerWaiting(String),
+    NotReady,
+    Restarted {
+        count: i32,
+        exit_code: i32,
+        reason: String,
+    },
+    TerminatedWithError(i32),
+}
+
+impl SuspiciousReason {
+    /// Human-readable explanation suitable for surfacing directly in the UI.
+    pub fn describe(&self) -> String {
+        match self {
+            SuspiciousReason::ContainerWaiting(reason) => format!("waiting: {}", reason),
+            SuspiciousReason::NotReady => "running but not ready".to_string(),
+            SuspiciousReason::Restarted {
+                count,
+                exit_code,
+                reason,
+            } => format!(
+                "restarted {} times (last exit {}: {})",
+                count, exit_code, reason
+            ),
+            SuspiciousReason::TerminatedWithError(exit_code) => {
+                format!("terminated with exit code {}", exit_code)
+            }
+        }
+    }
+}
+
+/// Classifies each container in `pod` into a suspicious-health reason, if
+/// any. A pod with an empty result is healthy. `restart_threshold` is the
+/// restart count, inclusive, considered a crash loop.
+pub fn classify_pod(pod: &Pod, restart_threshold: i32) -> Vec<(String, SuspiciousReason)> {
+    pod.status
+        .container_statuses
+        .iter()
+        .filter_map(|cs| {
+            classify_container(cs, &pod.status.phase, restart_threshold)
+                .map(|reason| (cs.name.clone(), reason))
+        })
+        .collect()
+}
+
+fn classify_container(
+    cs: &ContainerStatus,
+    pod_phase: &str,
+    restart_threshold: i32,
+) -> Option<SuspiciousReason> {
+    if let Some(ref waiting) = cs.state.waiting {
+        return Some(SuspiciousReason::ContainerWaiting(waiting.reason.clone()));
+    }
+
+    if cs.restart_count >= restart_threshold {
+        let (exit_code, reason) = cs
+            .last_state
+            .terminated
+            .as_ref()
+            .map(|t| (t.exit_code, t.reason.clone()))
+            .unwrap_or_default();
+        return Some(SuspiciousReason::Restarted {
+            count: cs.restart_count,
+            exit_code,
+            reason,
+        });
+    }
+
+    if let Some(ref terminated) = cs.state.terminated {
+        // Completed/Succeeded containers (exit 0) are healthy, not suspicious.
+        return if terminated.exit_code != 0 {
+            Some(SuspiciousReason::TerminatedWithError(terminated.exit_code))
+        } else {
+            None
+        };
+    }
+
+    if pod_phase == "Running" && !cs.ready {
+        return Some(SuspiciousReason::NotReady);
+    }
+
+    None
+}
@@ -1,5 +1,8 @@
 use chrono::{DateTime, Utc};
 
+use crate::clients::HealthState;
+use crate::models::pod_health::SuspiciousReason;
+
 #[derive(Debug, Clone, Default)]
 pub struct ClusterSummary {
     pub node_count: usize,
@@ -13,6 +16,8 @@ pub struct ClusterSummary {
 pub struct NodeSummary {
     pub name: String,
     pub healthy: bool,
+    pub health_state: HealthState,
+    pub consecutive_failures: u32,
     pub pod_count: usize,
     pub last_ping: Option<DateTime<Utc>>,
 }
@@ -28,6 +33,13 @@ pub struct PodView {
     pub age: String,
     pub containers: usize,
     pub ready: usize,
+    pub suspicious: Vec<(String, String)>,
+}
+
+impl PodView {
+    pub fn is_suspicious(&self) -> bool {
+        !self.suspicious.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -37,6 +49,7 @@ pub struct ContainerView {
     pub state: String,
     pub ready: bool,
     pub reason: String,
+    pub health: Option<SuspiciousReason>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -79,4 +92,27 @@ pub struct NodeView {
     pub architecture: String,
     pub board: String,
     pub cpu_load: String,
+    /// True once any of the standard pressure conditions report `True`, even
+    /// while the node is otherwise `Ready`.
+    pub degraded: bool,
+    pub memory_pressure: bool,
+    pub disk_pressure: bool,
+    pub pid_pressure: bool,
+    pub conditions: Vec<ConditionView>,
+}
+
+impl NodeView {
+    /// Whether the node should count as healthy: `Ready` and free of any
+    /// pressure condition.
+    pub fn is_healthy(&self) -> bool {
+        self.status == "Ready" && !self.degraded
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConditionView {
+    pub condition_type: String,
+    pub status: String,
+    pub reason: String,
+    pub message: String,
 }
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::sync::{RwLock, Semaphore};
+
+/// Caps the number of manifest/config-blob fetches in flight at once, so
+/// rendering the registry page against a large catalog doesn't open a
+/// connection per tag all at once.
+const MAX_CONCURRENT_MANIFEST_FETCHES: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct TagMetadata {
+    pub digest: String,
+    pub size_bytes: i64,
+    pub layer_count: usize,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestV2 {
+    config: ManifestDescriptor,
+    #[serde(default)]
+    layers: Vec<ManifestDescriptor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestDescriptor {
+    size: i64,
+    digest: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ImageConfig {
+    #[serde(default)]
+    created: Option<String>,
+}
+
+/// Fetches and caches OCI/Docker manifest metadata for registry tags, keyed
+/// by content digest. The manifest is fetched on every call to learn the
+/// tag's current digest; a tag that hasn't moved skips the config-blob fetch.
+pub struct RegistryCache {
+    by_digest: RwLock<HashMap<String, TagMetadata>>,
+    fetch_limit: Semaphore,
+}
+
+impl RegistryCache {
+    pub fn new() -> Self {
+        Self {
+            by_digest: RwLock::new(HashMap::new()),
+            fetch_limit: Semaphore::new(MAX_CONCURRENT_MANIFEST_FETCHES),
+        }
+    }
+
+    /// Resolves `repo:tag` to its manifest metadata, serving from cache when
+    /// the tag's current content digest is already known.
+    pub async fn fetch(&self, registry_url: &str, repo: &str, tag: &str) -> Option<TagMetadata> {
+        let _permit = self.fetch_limit.acquire().await.ok()?;
+
+        let client = reqwest::Client::new();
+        let resp = client
+            .get(format!("{}/v2/{}/manifests/{}", registry_url, repo, tag))
+            .header(
+                "Accept",
+                "application/vnd.docker.distribution.manifest.v2+json, application/vnd.oci.image.manifest.v1+json",
+            )
+            .send()
+            .await
+            .ok()?;
+
+        let digest_header = resp
+            .headers()
+            .get("Docker-Content-Digest")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let manifest: ManifestV2 = resp.json().await.ok()?;
+        let digest = digest_header.unwrap_or_else(|| manifest.config.digest.clone());
+
+        if let Some(cached) = self.by_digest.read().await.get(&digest).cloned() {
+            return Some(cached);
+        }
+
+        let size_bytes =
+            manifest.config.size + manifest.layers.iter().map(|l| l.size).sum::<i64>();
+        let layer_count = manifest.layers.len();
+
+        let created = client
+            .get(format!(
+                "{}/v2/{}/blobs/{}",
+                registry_url, repo, manifest.config.digest
+            ))
+            .send()
+            .await
+            .ok()?
+            .json::<ImageConfig>()
+            .await
+            .unwrap_or_default()
+            .created
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let metadata = TagMetadata {
+            digest: digest.clone(),
+            size_bytes,
+            layer_count,
+            created,
+        };
+
+        self.by_digest
+            .write()
+            .await
+            .insert(digest, metadata.clone());
+        Some(metadata)
+    }
+}
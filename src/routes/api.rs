@@ -1,11 +1,20 @@
 use axum::{
     Json,
-    extract::{Path, State},
+    body::Body,
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use bytes::Bytes;
+use futures_util::future;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
 
 use crate::models::k8s::*;
+use crate::selectors::{
+    self, FieldSelectorRequirement, LabelSelectorRequirement,
+};
 use crate::AppState;
 
 pub async fn handle_api_versions(State(state): State<AppState>) -> Json<ApiVersions> {
@@ -19,6 +28,64 @@ pub async fn handle_api_versions(State(state): State<AppState>) -> Json<ApiVersi
     })
 }
 
+/// `/apis` discovery, listing API groups beyond the core `v1` group exposed
+/// at `/api`. Currently just `metrics.k8s.io`, so `kubectl top` can find it.
+pub async fn handle_api_group_list() -> Json<ApiGroupList> {
+    let preferred = GroupVersionForDiscovery {
+        group_version: "metrics.k8s.io/v1beta1".to_string(),
+        version: "v1beta1".to_string(),
+    };
+    Json(ApiGroupList {
+        kind: "APIGroupList".to_string(),
+        groups: vec![ApiGroup {
+            name: "metrics.k8s.io".to_string(),
+            versions: vec![preferred.clone()],
+            preferred_version: preferred,
+        }],
+    })
+}
+
+pub async fn handle_metrics_api_resources() -> Json<ApiResourceList> {
+    Json(ApiResourceList {
+        kind: "APIResourceList".to_string(),
+        group_version: "metrics.k8s.io/v1beta1".to_string(),
+        api_resources: vec![
+            ApiResource {
+                name: "nodes".to_string(),
+                namespaced: false,
+                kind: "NodeMetrics".to_string(),
+                verbs: vec!["get".to_string(), "list".to_string()],
+            },
+            ApiResource {
+                name: "pods".to_string(),
+                namespaced: true,
+                kind: "PodMetrics".to_string(),
+                verbs: vec!["get".to_string(), "list".to_string()],
+            },
+        ],
+    })
+}
+
+pub async fn handle_list_node_metrics(State(state): State<AppState>) -> Json<NodeMetricsList> {
+    Json(state.metrics.node_metrics(&state.aggregator).await)
+}
+
+pub async fn handle_list_pod_metrics(State(state): State<AppState>) -> Json<PodMetricsList> {
+    Json(state.metrics.pod_metrics(&state.aggregator, None).await)
+}
+
+pub async fn handle_list_namespaced_pod_metrics(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+) -> Json<PodMetricsList> {
+    Json(
+        state
+            .metrics
+            .pod_metrics(&state.aggregator, Some(&namespace))
+            .await,
+    )
+}
+
 pub async fn handle_api_resources() -> Json<ApiResourceList> {
     Json(ApiResourceList {
         kind: "APIResourceList".to_string(),
@@ -47,6 +114,12 @@ pub async fn handle_api_resources() -> Json<ApiResourceList> {
                 kind: "Pod".to_string(),
                 verbs: vec!["get".to_string()],
             },
+            ApiResource {
+                name: "pods/exec".to_string(),
+                namespaced: true,
+                kind: "Pod".to_string(),
+                verbs: vec!["get".to_string(), "create".to_string()],
+            },
             ApiResource {
                 name: "namespaces".to_string(),
                 namespaced: false,
@@ -63,24 +136,76 @@ pub async fn handle_api_resources() -> Json<ApiResourceList> {
     })
 }
 
-pub async fn handle_list_all_pods(State(state): State<AppState>) -> Response {
+#[derive(Deserialize)]
+pub struct ListPodsQuery {
+    #[serde(default)]
+    pub watch: bool,
+    #[serde(default, rename = "resourceVersion")]
+    pub resource_version: Option<String>,
+    #[serde(default, rename = "labelSelector")]
+    pub label_selector: Option<String>,
+    #[serde(default, rename = "fieldSelector")]
+    pub field_selector: Option<String>,
+}
+
+/// Applies a pod list's `labelSelector`/`fieldSelector` query parameters,
+/// matching labels against `metadata.labels` and fields against the handful
+/// of pod fields real clients filter on (see [`selectors::pod_field_value`]).
+fn filter_pods(pods: Vec<Pod>, query: &ListPodsQuery) -> Vec<Pod> {
+    let label_reqs: Vec<LabelSelectorRequirement> = query
+        .label_selector
+        .as_deref()
+        .map(selectors::parse_label_selector)
+        .unwrap_or_default();
+    let field_reqs: Vec<FieldSelectorRequirement> = query
+        .field_selector
+        .as_deref()
+        .map(selectors::parse_field_selector)
+        .unwrap_or_default();
+
+    if label_reqs.is_empty() && field_reqs.is_empty() {
+        return pods;
+    }
+
+    pods.into_iter()
+        .filter(|p| {
+            let labels = p.metadata.labels.clone().unwrap_or_default();
+            selectors::matches_labels(&label_reqs, &labels)
+                && selectors::matches_fields(&field_reqs, |field| selectors::pod_field_value(p, field))
+        })
+        .collect()
+}
+
+pub async fn handle_list_all_pods(
+    State(state): State<AppState>,
+    Query(query): Query<ListPodsQuery>,
+) -> Response {
+    if query.watch {
+        return watch_pods_response(&state, None, query.resource_version).await;
+    }
+
     match state.aggregator.list_all_pods().await {
         Ok(pods) => Json(PodList {
             type_meta: TypeMeta {
                 api_version: "v1".to_string(),
                 kind: "PodList".to_string(),
             },
-            items: pods,
+            items: filter_pods(pods, &query),
         })
         .into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => internal_error_status(e),
     }
 }
 
 pub async fn handle_list_namespaced_pods(
     State(state): State<AppState>,
     Path(namespace): Path<String>,
+    Query(query): Query<ListPodsQuery>,
 ) -> Response {
+    if query.watch {
+        return watch_pods_response(&state, Some(namespace), query.resource_version).await;
+    }
+
     match state.aggregator.list_all_pods().await {
         Ok(pods) => {
             let items: Vec<Pod> = pods
@@ -92,21 +217,124 @@ pub async fn handle_list_namespaced_pods(
                     api_version: "v1".to_string(),
                     kind: "PodList".to_string(),
                 },
-                items,
+                items: filter_pods(items, &query),
             })
             .into_response()
         }
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => internal_error_status(e),
     }
 }
 
+/// Upgrades a pod list request into a long-lived newline-delimited JSON watch
+/// stream, Kubernetes-style: an initial burst of events for the caller to
+/// catch up on, followed by live events relayed from the aggregator's merged
+/// pod watch. This lets `kubectl get pods --watch` and informer-style
+/// clients follow changes without polling.
+///
+/// The initial burst is one of three things: with no `resourceVersion`, a
+/// synthetic `ADDED` event per pod in the current snapshot; with a
+/// `resourceVersion` still covered by the aggregator's replay buffer, the
+/// buffered events newer than it, so the caller sees exactly what it missed;
+/// with a `resourceVersion` older than everything buffered, a full relist
+/// (same as no `resourceVersion` at all) since the gap can no longer be
+/// replayed precisely.
+async fn watch_pods_response(
+    state: &AppState,
+    namespace: Option<String>,
+    resource_version: Option<String>,
+) -> Response {
+    let rx = state.aggregator.subscribe_pod_watch();
+
+    let relist = || async {
+        state
+            .aggregator
+            .list_all_pods()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| {
+                namespace
+                    .as_deref()
+                    .map(|ns| ns == p.metadata.namespace)
+                    .unwrap_or(true)
+            })
+            .map(|object| PodWatchEvent {
+                event_type: "ADDED".to_string(),
+                object,
+            })
+            .collect::<Vec<PodWatchEvent>>()
+    };
+
+    let initial: Vec<PodWatchEvent> = match &resource_version {
+        None => relist().await,
+        Some(rv) => match state.aggregator.pod_events_since(rv).await {
+            Some(events) => events
+                .into_iter()
+                .filter(|ev| {
+                    namespace
+                        .as_deref()
+                        .map(|ns| ns == ev.object.metadata.namespace)
+                        .unwrap_or(true)
+                })
+                .collect(),
+            None => {
+                tracing::warn!(
+                    "pod watch resume from resourceVersion {} predates the replay buffer, falling back to a full relist",
+                    rv
+                );
+                relist().await
+            }
+        },
+    };
+
+    let initial_stream = stream::iter(initial);
+
+    let live_stream = stream::unfold((rx, namespace), |(mut rx, namespace)| async move {
+        loop {
+            match rx.recv().await {
+                Ok(ev) => {
+                    if ev.event_type != "BOOKMARK" {
+                        if let Some(ref ns) = namespace {
+                            if &ev.object.metadata.namespace != ns {
+                                continue;
+                            }
+                        }
+                    }
+                    return Some((ev, (rx, namespace)));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("watch client lagged by {} events, closing the watch so it resyncs", skipped);
+                    return None;
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let body_stream =
+        initial_stream
+            .chain(live_stream)
+            .map(|ev| -> Result<Bytes, std::io::Error> {
+                let mut line = serde_json::to_vec(&ev).unwrap_or_default();
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            });
+
+    (
+        StatusCode::OK,
+        [("content-type", "application/json")],
+        Body::from_stream(body_stream),
+    )
+        .into_response()
+}
+
 pub async fn handle_get_pod(
     State(state): State<AppState>,
     Path((namespace, name)): Path<(String, String)>,
 ) -> Response {
     match state.aggregator.get_pod(&namespace, &name).await {
         Ok((pod, _)) => Json(pod).into_response(),
-        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+        Err(e) => found_resource_error_response("Pod", &name, e),
     }
 }
 
@@ -116,9 +344,10 @@ pub async fn handle_create_pod(
     Json(mut pod): Json<Pod>,
 ) -> Response {
     pod.metadata.namespace = namespace;
+    let name = pod.metadata.name.clone();
     match state.aggregator.create_pod(&pod).await {
         Ok(result) => (StatusCode::CREATED, Json(result)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => create_pod_error_response(&name, e),
     }
 }
 
@@ -127,56 +356,392 @@ pub async fn handle_delete_pod(
     Path((namespace, name)): Path<(String, String)>,
 ) -> Response {
     match state.aggregator.delete_pod(&namespace, &name).await {
-        Ok(()) => Json(Status {
-            api_version: "v1".to_string(),
-            kind: "Status".to_string(),
-            status: "Success".to_string(),
-            message: format!("pod {:?} deleted", name),
-        })
-        .into_response(),
-        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+        Ok(()) => Json(success_status(format!("pod {:?} deleted", name))).into_response(),
+        Err(e) => found_resource_error_response("Pod", &name, e),
+    }
+}
+
+/// Maps an error from an aggregator call that resolves an *existing*
+/// resource (get/delete a pod, get a node) to the right `Status` reason. A
+/// [`crate::clients::NodeApiError`] carrying a 404 means the node
+/// genuinely doesn't have it; anything else the node reported (5xx,
+/// unreachable, etc.) is the node's own failure, not a "not found", so it's
+/// surfaced as `InternalError` instead. An error with no status code at all
+/// comes from the aggregator itself (e.g. "pod not found on any node"),
+/// which is a real not-found case.
+fn found_resource_error_response(kind: &str, name: &str, source: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    if source.downcast_ref::<reqwest::Error>().is_some() {
+        return internal_error_status(source);
+    }
+    match source.downcast_ref::<crate::clients::NodeApiError>().map(|e| e.status) {
+        None | Some(404) => not_found_status(kind, name, source),
+        Some(_) => internal_error_status(source),
+    }
+}
+
+/// Maps an error from `Aggregator::create_pod` to the right `Status` reason:
+/// a [`crate::clients::NodeApiError`] carrying a 409 means the node already
+/// has a pod by that name, so it's `AlreadyExists`/`Conflict` rather than a
+/// generic failure; everything else (scheduling errors, node 5xx) is
+/// `InternalError`.
+fn create_pod_error_response(name: &str, source: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    match source.downcast_ref::<crate::clients::NodeApiError>().map(|e| e.status) {
+        Some(409) => error_status(
+            StatusCode::CONFLICT,
+            "AlreadyExists",
+            format!("pod {:?} already exists", name),
+            Some(StatusDetails {
+                name: name.to_string(),
+                kind: "Pod".to_string(),
+            }),
+        ),
+        _ => internal_error_status(source),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchPodTarget {
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct BatchDeletePodsRequest {
+    pub targets: Vec<BatchPodTarget>,
+}
+
+#[derive(Serialize)]
+pub struct BatchDeleteResult {
+    pub target: BatchPodTarget,
+    pub status: &'static str,
+    pub message: String,
+}
+
+/// Deletes many pods in one round trip, dispatching through the aggregator
+/// concurrently and reporting a per-item status rather than failing the
+/// whole batch when one target errors.
+pub async fn handle_batch_delete_pods(
+    State(state): State<AppState>,
+    Json(req): Json<BatchDeletePodsRequest>,
+) -> Response {
+    let results: Vec<BatchDeleteResult> =
+        future::join_all(req.targets.into_iter().map(|target| {
+            let state = state.clone();
+            async move {
+                match state
+                    .aggregator
+                    .delete_pod(&target.namespace, &target.name)
+                    .await
+                {
+                    Ok(()) => BatchDeleteResult {
+                        target,
+                        status: "ok",
+                        message: "deleted".to_string(),
+                    },
+                    Err(e) => BatchDeleteResult {
+                        target,
+                        status: "error",
+                        message: e.to_string(),
+                    },
+                }
+            }
+        }))
+        .await;
+
+    Json(results).into_response()
+}
+
+#[derive(Deserialize, Default)]
+pub struct PodLogQuery {
+    #[serde(default)]
+    pub container: String,
+    #[serde(default)]
+    pub follow: bool,
+    #[serde(default, rename = "tailLines")]
+    pub tail_lines: Option<i64>,
+    #[serde(default, rename = "sinceSeconds")]
+    pub since_seconds: Option<i64>,
+    #[serde(default, rename = "sinceTime")]
+    pub since_time: Option<String>,
+    #[serde(default)]
+    pub timestamps: bool,
+    #[serde(default)]
+    pub previous: bool,
+}
+
+impl From<PodLogQuery> for crate::clients::LogOptions {
+    fn from(q: PodLogQuery) -> Self {
+        crate::clients::LogOptions {
+            container: q.container,
+            follow: q.follow,
+            tail_lines: q.tail_lines,
+            since_seconds: q.since_seconds,
+            since_time: q.since_time,
+            timestamps: q.timestamps,
+            previous: q.previous,
+        }
     }
 }
 
 pub async fn handle_get_pod_log(
     State(state): State<AppState>,
     Path((namespace, name)): Path<(String, String)>,
+    Query(query): Query<PodLogQuery>,
 ) -> Response {
-    match state.aggregator.get_pod_log(&namespace, &name).await {
+    let follow = query.follow;
+    let opts: crate::clients::LogOptions = query.into();
+
+    if follow {
+        return match state.aggregator.stream_pod_log(&namespace, &name, &opts).await {
+            Ok(stream) => (
+                StatusCode::OK,
+                [("content-type", "text/plain; charset=utf-8")],
+                Body::from_stream(stream),
+            )
+                .into_response(),
+            Err(e) => found_resource_error_response("Pod", &name, e),
+        };
+    }
+
+    match state.aggregator.get_pod_log(&namespace, &name, &opts).await {
         Ok(logs) => (
             StatusCode::OK,
             [("content-type", "text/plain; charset=utf-8")],
             logs,
         )
             .into_response(),
-        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+        Err(e) => found_resource_error_response("Pod", &name, e),
     }
 }
 
-pub async fn handle_list_nodes(State(state): State<AppState>) -> Response {
+#[derive(Deserialize)]
+pub struct ListNodesQuery {
+    #[serde(default)]
+    pub watch: bool,
+    #[serde(default, rename = "resourceVersion")]
+    pub resource_version: Option<String>,
+    #[serde(default, rename = "labelSelector")]
+    pub label_selector: Option<String>,
+    #[serde(default, rename = "fieldSelector")]
+    pub field_selector: Option<String>,
+}
+
+/// Node-list equivalent of [`filter_pods`], matching fields against
+/// [`selectors::node_field_value`] instead.
+fn filter_nodes(nodes: Vec<Node>, query: &ListNodesQuery) -> Vec<Node> {
+    let label_reqs: Vec<LabelSelectorRequirement> = query
+        .label_selector
+        .as_deref()
+        .map(selectors::parse_label_selector)
+        .unwrap_or_default();
+    let field_reqs: Vec<FieldSelectorRequirement> = query
+        .field_selector
+        .as_deref()
+        .map(selectors::parse_field_selector)
+        .unwrap_or_default();
+
+    if label_reqs.is_empty() && field_reqs.is_empty() {
+        return nodes;
+    }
+
+    nodes
+        .into_iter()
+        .filter(|n| {
+            let labels = n.metadata.labels.clone().unwrap_or_default();
+            selectors::matches_labels(&label_reqs, &labels)
+                && selectors::matches_fields(&field_reqs, |field| selectors::node_field_value(n, field))
+        })
+        .collect()
+}
+
+pub async fn handle_list_nodes(
+    State(state): State<AppState>,
+    Query(query): Query<ListNodesQuery>,
+) -> Response {
+    if query.watch {
+        return watch_nodes_response(&state, query.resource_version).await;
+    }
+
     match state.aggregator.list_all_nodes().await {
         Ok(nodes) => Json(NodeList {
             type_meta: TypeMeta {
                 api_version: "v1".to_string(),
                 kind: "NodeList".to_string(),
             },
-            items: nodes,
+            items: filter_nodes(nodes, &query),
         })
         .into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => internal_error_status(e),
     }
 }
 
+/// Same shape as [`watch_pods_response`] but for the node list, relaying the
+/// aggregator's poll-and-diff node watch instead of the pod event stream.
+/// See that function's doc comment for how the initial burst is chosen
+/// between a full relist and a replay from the aggregator's event buffer.
+async fn watch_nodes_response(state: &AppState, resource_version: Option<String>) -> Response {
+    let rx = state.aggregator.subscribe_node_watch();
+
+    let relist = || async {
+        state
+            .aggregator
+            .list_all_nodes()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|object| NodeWatchEvent {
+                event_type: "ADDED".to_string(),
+                object,
+            })
+            .collect::<Vec<NodeWatchEvent>>()
+    };
+
+    let initial: Vec<NodeWatchEvent> = match &resource_version {
+        None => relist().await,
+        Some(rv) => match state.aggregator.node_events_since(rv).await {
+            Some(events) => events,
+            None => {
+                tracing::warn!(
+                    "node watch resume from resourceVersion {} predates the replay buffer, falling back to a full relist",
+                    rv
+                );
+                relist().await
+            }
+        },
+    };
+
+    let initial_stream = stream::iter(initial);
+
+    let live_stream = stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(ev) => return Some((ev, rx)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("node watch client lagged by {} events, closing the watch so it resyncs", skipped);
+                    return None;
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let body_stream =
+        initial_stream
+            .chain(live_stream)
+            .map(|ev| -> Result<Bytes, std::io::Error> {
+                let mut line = serde_json::to_vec(&ev).unwrap_or_default();
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            });
+
+    (
+        StatusCode::OK,
+        [("content-type", "application/json")],
+        Body::from_stream(body_stream),
+    )
+        .into_response()
+}
+
 pub async fn handle_get_node(
     State(state): State<AppState>,
     Path(name): Path<String>,
 ) -> Response {
     match state.aggregator.get_node(&name).await {
         Ok(node) => Json(node).into_response(),
-        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+        Err(e) => found_resource_error_response("Node", &name, e),
     }
 }
 
 pub async fn handle_healthz() -> &'static str {
     "ok\n"
 }
+
+#[derive(Deserialize)]
+pub struct RegisterNodeRequest {
+    pub name: String,
+    pub address: String,
+}
+
+/// Registers a node at runtime (or refreshes its heartbeat if it's already
+/// known), so the node fleet can scale without restarting the console.
+pub async fn handle_register_node(
+    State(state): State<AppState>,
+    Json(req): Json<RegisterNodeRequest>,
+) -> Response {
+    state
+        .aggregator
+        .add_client(req.name.clone(), req.address)
+        .await;
+    Json(success_status(format!("node {:?} registered", req.name))).into_response()
+}
+
+pub async fn handle_deregister_node(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Response {
+    state.aggregator.remove_client(&name).await;
+    Json(success_status(format!("node {:?} deregistered", name))).into_response()
+}
+
+/// Builds a `Success` `Status` object for handlers that report a bare
+/// confirmation message rather than the mutated resource itself.
+fn success_status(message: String) -> Status {
+    Status {
+        api_version: "v1".to_string(),
+        kind: "Status".to_string(),
+        status: "Success".to_string(),
+        message,
+        reason: String::new(),
+        code: Some(StatusCode::OK.as_u16() as i32),
+        details: None,
+    }
+}
+
+/// Builds a `Failure` `Status` response the way the real API server does:
+/// JSON body with `reason`/`code`/`details`, not a bare text string, so
+/// `kubectl`/client-go can print a meaningful message and key retry logic
+/// off the structured reason.
+fn error_status(
+    code: StatusCode,
+    reason: &str,
+    message: String,
+    details: Option<StatusDetails>,
+) -> Response {
+    (
+        code,
+        Json(Status {
+            api_version: "v1".to_string(),
+            kind: "Status".to_string(),
+            status: "Failure".to_string(),
+            message,
+            reason: reason.to_string(),
+            code: Some(code.as_u16() as i32),
+            details,
+        }),
+    )
+        .into_response()
+}
+
+/// Most of this proxy's "not found" errors come from the aggregator as a
+/// plain formatted string; this renders them the way kubectl expects,
+/// `<kind> "<name>" not found`, and tags the response `reason: NotFound`.
+fn not_found_status(kind: &str, name: &str, _source: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    error_status(
+        StatusCode::NOT_FOUND,
+        "NotFound",
+        format!("{} {:?} not found", kind.to_lowercase(), name),
+        Some(StatusDetails {
+            name: name.to_string(),
+            kind: kind.to_string(),
+        }),
+    )
+}
+
+fn internal_error_status(source: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    error_status(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "InternalError",
+        source.to_string(),
+        None,
+    )
+}
@@ -0,0 +1,176 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    response::Response,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio_tungstenite::tungstenite::Message as BackendMessage;
+
+use crate::AppState;
+
+/// Channel bytes of the `v4.channel.k8s.io` exec protocol.
+const CHANNEL_STDIN: u8 = 0;
+const CHANNEL_STDOUT: u8 = 1;
+const CHANNEL_STDERR: u8 = 2;
+const CHANNEL_ERROR: u8 = 3;
+const CHANNEL_RESIZE: u8 = 4;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ExecQuery {
+    #[serde(default)]
+    pub container: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub stdin: bool,
+    #[serde(default)]
+    pub stdout: bool,
+    #[serde(default)]
+    pub stderr: bool,
+    #[serde(default)]
+    pub tty: bool,
+}
+
+/// Upgrades the connection to a WebSocket negotiating the `v4.channel.k8s.io`
+/// subprotocol and bridges it to the backing node's exec session.
+pub async fn handle_exec(
+    State(state): State<AppState>,
+    Path((namespace, name)): Path<(String, String)>,
+    Query(query): Query<ExecQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.protocols(["v4.channel.k8s.io"])
+        .on_upgrade(move |socket| bridge_exec(state, namespace, name, query, socket))
+}
+
+/// Pumps frames in both directions for the lifetime of the session: client
+/// channel-0 bytes become backend stdin, and the backend's Docker-framed
+/// stdout/stderr becomes client channel-1/2 frames.
+async fn bridge_exec(state: AppState, namespace: String, name: String, query: ExecQuery, mut client_ws: WebSocket) {
+    let backend = match state
+        .aggregator
+        .exec_pod(&namespace, &name, &query.container, &query.command, query.tty)
+        .await
+    {
+        Ok(backend) => backend,
+        Err(e) => {
+            send_error(&mut client_ws, &e.to_string()).await;
+            return;
+        }
+    };
+
+    let (mut backend_write, mut backend_read) = backend.split();
+
+    // Docker stdcopy frames can arrive split across WebSocket messages (a
+    // header cut in half, or a payload larger than one message), so bytes
+    // that don't yet form a complete frame are held here until the rest
+    // arrives.
+    let mut demux_buf: Vec<u8> = Vec::new();
+
+    loop {
+        tokio::select! {
+            msg = client_ws.next() => {
+                match msg {
+                    Some(Ok(Message::Binary(data))) if !data.is_empty() => {
+                        match data[0] {
+                            CHANNEL_STDIN => {
+                                if backend_write
+                                    .send(BackendMessage::Binary(data[1..].to_vec()))
+                                    .await
+                                    .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            CHANNEL_RESIZE => {
+                                // The node-agent exec endpoint has no TTY resize
+                                // control channel today, so this is a no-op.
+                            }
+                            _ => {}
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Err(_)) => break,
+                    _ => {}
+                }
+            }
+            frame = backend_read.next() => {
+                match frame {
+                    Some(Ok(BackendMessage::Binary(data))) => {
+                        // A TTY attach is a raw byte stream, not stdcopy-framed
+                        // (Docker only multiplexes stdout/stderr when there's no
+                        // TTY), so demuxing it would parse real output as bogus
+                        // frame headers. Forward it straight through as stdout.
+                        if query.tty {
+                            let mut out = vec![CHANNEL_STDOUT];
+                            out.extend_from_slice(&data);
+                            if client_ws.send(Message::Binary(out)).await.is_err() {
+                                return;
+                            }
+                        } else {
+                            demux_buf.extend_from_slice(&data);
+                            for (channel, chunk) in drain_docker_frames(&mut demux_buf) {
+                                let mut out = vec![channel];
+                                out.extend_from_slice(&chunk);
+                                if client_ws.send(Message::Binary(out)).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    Some(Ok(BackendMessage::Close(_))) | None => break,
+                    Some(Err(_)) => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let _ = client_ws.close().await;
+}
+
+async fn send_error(client_ws: &mut WebSocket, message: &str) {
+    let status = serde_json::json!({
+        "kind": "Status",
+        "apiVersion": "v1",
+        "status": "Failure",
+        "message": message,
+    });
+    let mut frame = vec![CHANNEL_ERROR];
+    frame.extend_from_slice(status.to_string().as_bytes());
+    let _ = client_ws.send(Message::Binary(frame)).await;
+}
+
+/// Drains complete Docker stdcopy frames — repeated `[stream_type, 0, 0, 0,
+/// size_be_u32]` headers each followed by `size` bytes of payload — off the
+/// front of `buf` into `(k8s_channel, payload)` pairs ready to re-emit on the
+/// client socket. `buf` persists across calls so a header or payload split
+/// across WebSocket messages is completed rather than dropped: any trailing
+/// bytes that don't yet form a whole frame are left in `buf` for next time.
+fn drain_docker_frames(buf: &mut Vec<u8>) -> Vec<(u8, Vec<u8>)> {
+    let mut out = Vec::new();
+    let mut consumed = 0;
+
+    while buf.len() - consumed >= 8 {
+        let header = &buf[consumed..consumed + 8];
+        let stream_type = header[0];
+        let size = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+        if buf.len() - consumed - 8 < size {
+            // Payload hasn't fully arrived yet; wait for more data.
+            break;
+        }
+
+        let start = consumed + 8;
+        let end = start + size;
+        let channel = if stream_type == 2 { CHANNEL_STDERR } else { CHANNEL_STDOUT };
+        out.push((channel, buf[start..end].to_vec()));
+        consumed = end;
+    }
+
+    buf.drain(..consumed);
+    out
+}
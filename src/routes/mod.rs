@@ -1,10 +1,11 @@
 pub mod api;
+pub mod exec;
 pub mod sse;
 pub mod ui;
 
 use axum::{
     Router,
-    routing::get,
+    routing::{get, post},
 };
 use tower_http::services::ServeDir;
 
@@ -15,6 +16,24 @@ pub fn build_router(state: AppState) -> Router {
         // API discovery
         .route("/api", get(api::handle_api_versions))
         .route("/api/v1", get(api::handle_api_resources))
+        .route("/apis", get(api::handle_api_group_list))
+        // Metrics (metrics.k8s.io/v1beta1), for `kubectl top`
+        .route(
+            "/apis/metrics.k8s.io/v1beta1",
+            get(api::handle_metrics_api_resources),
+        )
+        .route(
+            "/apis/metrics.k8s.io/v1beta1/nodes",
+            get(api::handle_list_node_metrics),
+        )
+        .route(
+            "/apis/metrics.k8s.io/v1beta1/pods",
+            get(api::handle_list_pod_metrics),
+        )
+        .route(
+            "/apis/metrics.k8s.io/v1beta1/namespaces/{namespace}/pods",
+            get(api::handle_list_namespaced_pod_metrics),
+        )
         // Pods
         .route("/api/v1/pods", get(api::handle_list_all_pods))
         .route(
@@ -25,13 +44,27 @@ pub fn build_router(state: AppState) -> Router {
             "/api/v1/namespaces/{namespace}/pods/{name}",
             get(api::handle_get_pod).delete(api::handle_delete_pod),
         )
+        .route(
+            "/api/v1/batch/pods:delete",
+            post(api::handle_batch_delete_pods),
+        )
         .route(
             "/api/v1/namespaces/{namespace}/pods/{name}/log",
             get(api::handle_get_pod_log),
         )
+        .route(
+            "/api/v1/namespaces/{namespace}/pods/{name}/exec",
+            get(exec::handle_exec),
+        )
         // Nodes
         .route("/api/v1/nodes", get(api::handle_list_nodes))
         .route("/api/v1/nodes/{name}", get(api::handle_get_node))
+        // Node membership (runtime registration/eviction)
+        .route("/api/v1/register", post(api::handle_register_node))
+        .route(
+            "/api/v1/register/{name}",
+            axum::routing::delete(api::handle_deregister_node),
+        )
         // Health
         .route("/healthz", get(api::handle_healthz))
         // Dashboard UI
@@ -42,11 +75,13 @@ pub fn build_router(state: AppState) -> Router {
         .route("/ui/namespaces/{namespace}/pods/{pod}/containers/{name}", get(ui::handle_container_detail))
         // SSE events
         .route("/ui/events/pods", get(sse::handle_pod_events))
+        .route("/ui/events/nodes", get(sse::handle_node_events))
         .route("/ui/pods", get(ui::handle_pods))
         .route("/ui/pods/{namespace}/{name}", get(ui::handle_pod_detail))
         .route("/ui/nodes", get(ui::handle_nodes))
         .route("/ui/nodes/{name}", get(ui::handle_node_detail))
         .route("/ui/registry", get(ui::handle_registry))
+        .route("/ui/health", get(ui::handle_health))
         // Static files
         .nest_service("/ui/static", ServeDir::new("static"))
         // Root redirect
@@ -9,6 +9,7 @@ use std::collections::{BTreeSet, HashMap};
 
 use crate::helpers::{human_bytes, human_time, parse_age};
 use crate::models::k8s;
+use crate::models::pod_health::{self, SuspiciousReason};
 use crate::models::views::*;
 use crate::AppState;
 
@@ -80,7 +81,7 @@ pub async fn handle_namespaces(State(state): State<AppState>) -> Response {
 
 // --- View Builders ---
 
-fn build_pod_view(pod: &k8s::Pod) -> PodView {
+fn build_pod_view(pod: &k8s::Pod, restart_threshold: i32) -> PodView {
     let mut pv = PodView {
         name: pod.metadata.name.clone(),
         namespace: pod.metadata.namespace.clone(),
@@ -112,6 +113,14 @@ fn build_pod_view(pod: &k8s::Pod) -> PodView {
     }
     .to_string();
 
+    pv.suspicious = pod_health::classify_pod(pod, restart_threshold)
+        .into_iter()
+        .map(|(name, reason)| (name, reason.describe()))
+        .collect();
+    if pv.is_suspicious() && pv.status_class == "badge-success" {
+        pv.status_class = "badge-warning".to_string();
+    }
+
     pv
 }
 
@@ -125,15 +134,34 @@ fn build_node_view(node: &k8s::Node) -> NodeView {
     };
 
     for cond in &node.status.conditions {
-        if cond.condition_type == "Ready" {
-            if cond.status == "True" {
-                nv.status = "Ready".to_string();
-                nv.status_class = "badge-success".to_string();
-            } else {
-                nv.status = "NotReady".to_string();
-                nv.status_class = "badge-error".to_string();
+        match cond.condition_type.as_str() {
+            "Ready" => {
+                if cond.status == "True" {
+                    nv.status = "Ready".to_string();
+                    nv.status_class = "badge-success".to_string();
+                } else {
+                    nv.status = "NotReady".to_string();
+                    nv.status_class = "badge-error".to_string();
+                }
             }
+            "MemoryPressure" => nv.memory_pressure = cond.status == "True",
+            "DiskPressure" => nv.disk_pressure = cond.status == "True",
+            "PIDPressure" => nv.pid_pressure = cond.status == "True",
+            _ => {}
         }
+
+        nv.conditions.push(ConditionView {
+            condition_type: cond.condition_type.clone(),
+            status: cond.status.clone(),
+            reason: cond.reason.clone(),
+            message: cond.message.clone(),
+        });
+    }
+
+    nv.degraded = nv.memory_pressure || nv.disk_pressure || nv.pid_pressure;
+    if nv.degraded && nv.status == "Ready" {
+        nv.status = "Degraded".to_string();
+        nv.status_class = "badge-warning".to_string();
     }
 
     if let Some(cpu) = node.status.capacity.get("cpu") {
@@ -168,7 +196,12 @@ fn build_node_view(node: &k8s::Node) -> NodeView {
     nv
 }
 
-fn build_container_views(pod: &k8s::Pod) -> Vec<ContainerView> {
+fn build_container_views(pod: &k8s::Pod, restart_threshold: i32) -> Vec<ContainerView> {
+    let health_by_container: HashMap<String, SuspiciousReason> =
+        pod_health::classify_pod(pod, restart_threshold)
+            .into_iter()
+            .collect();
+
     pod.status
         .container_statuses
         .iter()
@@ -188,6 +221,7 @@ fn build_container_views(pod: &k8s::Pod) -> Vec<ContainerView> {
                 state,
                 ready: cs.ready,
                 reason,
+                health: health_by_container.get(&cs.name).cloned(),
             }
         })
         .collect()
@@ -252,20 +286,35 @@ struct DashboardTemplate {
 pub async fn handle_dashboard(State(state): State<AppState>) -> Response {
     let summary = state.aggregator.get_cluster_summary().await;
 
+    let restart_threshold = state.config.pod_health.restart_threshold;
     let pods = state.aggregator.list_all_pods().await.unwrap_or_default();
-    let recent_pods: Vec<PodView> = pods.iter().take(10).map(build_pod_view).collect();
+    let recent_pods: Vec<PodView> = pods
+        .iter()
+        .take(10)
+        .map(|p| build_pod_view(p, restart_threshold))
+        .collect();
+
+    let all_nodes = state.aggregator.list_all_nodes().await.unwrap_or_default();
+    let degraded_by_name: std::collections::HashSet<String> = all_nodes
+        .iter()
+        .map(build_node_view)
+        .filter(|nv| nv.degraded)
+        .map(|nv| nv.name)
+        .collect();
 
     let nodes: Vec<DashboardNodeView> = summary
         .nodes
         .iter()
         .map(|n| DashboardNodeView {
             name: n.name.clone(),
-            healthy: n.healthy,
+            healthy: n.healthy && !degraded_by_name.contains(&n.name),
             pod_count: n.pod_count,
             last_ping_display: human_time(n.last_ping),
         })
         .collect();
 
+    let healthy_nodes = nodes.iter().filter(|n| n.healthy).count();
+
     let tmpl = DashboardTemplate {
         title: "Dashboard".to_string(),
         current_nav: "dashboard".to_string(),
@@ -274,7 +323,7 @@ pub async fn handle_dashboard(State(state): State<AppState>) -> Response {
             url: "/ui/".to_string(),
         }],
         node_count: summary.node_count,
-        healthy_nodes: summary.healthy_nodes,
+        healthy_nodes,
         pod_count: summary.pod_count,
         running_pods: summary.running_pods,
         nodes,
@@ -308,6 +357,7 @@ pub async fn handle_pods(
     Query(query): Query<PodQuery>,
 ) -> Response {
     let ns_filter = query.namespace.unwrap_or_default();
+    let restart_threshold = state.config.pod_health.restart_threshold;
     let all_pods = state.aggregator.list_all_pods().await.unwrap_or_default();
 
     let mut namespaces = BTreeSet::new();
@@ -318,7 +368,7 @@ pub async fn handle_pods(
         if !ns_filter.is_empty() && pod.metadata.namespace != ns_filter {
             continue;
         }
-        pod_views.push(build_pod_view(pod));
+        pod_views.push(build_pod_view(pod, restart_threshold));
     }
 
     let tmpl = PodsTemplate {
@@ -368,8 +418,9 @@ pub async fn handle_pod_detail(
         Err(_) => return (StatusCode::NOT_FOUND, "Pod not found").into_response(),
     };
 
-    let pv = build_pod_view(&pod);
-    let containers = build_container_views(&pod);
+    let restart_threshold = state.config.pod_health.restart_threshold;
+    let pv = build_pod_view(&pod, restart_threshold);
+    let containers = build_container_views(&pod, restart_threshold);
     let volumes = build_volume_views(&pod);
 
     let tmpl = PodDetailTemplate {
@@ -457,6 +508,7 @@ pub async fn handle_node_detail(
 
     let nv = build_node_view(&k8s_node);
 
+    let restart_threshold = state.config.pod_health.restart_threshold;
     let all_pods = state.aggregator.list_all_pods().await.unwrap_or_default();
     let pod_views: Vec<PodView> = all_pods
         .iter()
@@ -468,7 +520,7 @@ pub async fn handle_node_detail(
                 .map(|n| n == &name)
                 .unwrap_or(false)
         })
-        .map(build_pod_view)
+        .map(|p| build_pod_view(p, restart_threshold))
         .collect();
 
     let tmpl = NodeDetailTemplate {
@@ -497,10 +549,19 @@ pub async fn handle_node_detail(
 
 // --- Registry ---
 
+#[derive(Debug, Clone, Default)]
+pub struct TagView {
+    pub tag: String,
+    pub digest: String,
+    pub size: String,
+    pub layer_count: usize,
+    pub created: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct RepoView {
     pub name: String,
-    pub tags: Vec<String>,
+    pub tags: Vec<TagView>,
 }
 
 #[derive(Template)]
@@ -521,7 +582,16 @@ pub async fn handle_registry(State(state): State<AppState>) -> Response {
     if available {
         if let Some(catalog) = fetch_catalog(&registry_url).await {
             for repo_name in catalog {
-                let tags = fetch_tags(&registry_url, &repo_name).await;
+                let tag_names = fetch_tags(&registry_url, &repo_name).await;
+
+                let mut tags: Vec<TagView> = futures_util::future::join_all(
+                    tag_names
+                        .iter()
+                        .map(|tag| build_tag_view(&state, &registry_url, &repo_name, tag)),
+                )
+                .await;
+
+                tags.sort_by(|a, b| b.created.cmp(&a.created));
                 repos.push(RepoView {
                     name: repo_name,
                     tags,
@@ -550,6 +620,27 @@ pub async fn handle_registry(State(state): State<AppState>) -> Response {
     render_template(&tmpl)
 }
 
+async fn build_tag_view(state: &AppState, registry_url: &str, repo: &str, tag: &str) -> TagView {
+    let metadata = state.registry_cache.fetch(registry_url, repo, tag).await;
+
+    match metadata {
+        Some(m) => TagView {
+            tag: tag.to_string(),
+            digest: m.digest,
+            size: human_bytes(m.size_bytes),
+            layer_count: m.layer_count,
+            created: m
+                .created
+                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_default(),
+        },
+        None => TagView {
+            tag: tag.to_string(),
+            ..Default::default()
+        },
+    }
+}
+
 async fn fetch_catalog(registry_url: &str) -> Option<Vec<String>> {
     #[derive(Deserialize)]
     struct Catalog {
@@ -593,8 +684,12 @@ struct LogsTemplate {
 }
 
 pub async fn handle_logs(State(state): State<AppState>) -> Response {
+    let restart_threshold = state.config.pod_health.restart_threshold;
     let all_pods = state.aggregator.list_all_pods().await.unwrap_or_default();
-    let pod_views: Vec<PodView> = all_pods.iter().map(build_pod_view).collect();
+    let pod_views: Vec<PodView> = all_pods
+        .iter()
+        .map(|p| build_pod_view(p, restart_threshold))
+        .collect();
 
     let tmpl = LogsTemplate {
         title: "Logs".to_string(),
@@ -615,3 +710,59 @@ pub async fn handle_logs(State(state): State<AppState>) -> Response {
 
     render_template(&tmpl)
 }
+
+// --- Health ---
+
+#[derive(Debug, Clone)]
+pub struct SuspiciousPodView {
+    pub pod: PodView,
+    pub reasons: Vec<(String, String)>,
+}
+
+#[derive(Template)]
+#[template(path = "health.html")]
+#[allow(dead_code)]
+struct HealthTemplate {
+    title: String,
+    current_nav: String,
+    breadcrumbs: Vec<Breadcrumb>,
+    suspicious_pods: Vec<SuspiciousPodView>,
+    healthy_count: usize,
+}
+
+pub async fn handle_health(State(state): State<AppState>) -> Response {
+    let restart_threshold = state.config.pod_health.restart_threshold;
+    let all_pods = state.aggregator.list_all_pods().await.unwrap_or_default();
+
+    let mut suspicious_pods = Vec::new();
+    let mut healthy_count = 0;
+
+    for pod in &all_pods {
+        let pv = build_pod_view(pod, restart_threshold);
+        if pv.is_suspicious() {
+            let reasons = pv.suspicious.clone();
+            suspicious_pods.push(SuspiciousPodView { pod: pv, reasons });
+        } else {
+            healthy_count += 1;
+        }
+    }
+
+    let tmpl = HealthTemplate {
+        title: "Health".to_string(),
+        current_nav: "health".to_string(),
+        breadcrumbs: vec![
+            Breadcrumb {
+                label: "Dashboard".to_string(),
+                url: "/ui/".to_string(),
+            },
+            Breadcrumb {
+                label: "Health".to_string(),
+                url: "/ui/health".to_string(),
+            },
+        ],
+        suspicious_pods,
+        healthy_count,
+    };
+
+    render_template(&tmpl)
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::helpers::{parse_cpu_millis, parse_memory_bytes};
+use crate::models::k8s::Pod;
+
+/// A node's current standing for a scheduling decision. Pod counts and
+/// summed requests come from already-cached cluster state; labels and
+/// allocatable capacity come from a `get_node` fetch per candidate.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub name: String,
+    pub labels: HashMap<String, String>,
+    pub pod_count: usize,
+    pub allocatable_cpu_millis: i64,
+    pub allocatable_memory_bytes: i64,
+    pub requested_cpu_millis: i64,
+    pub requested_memory_bytes: i64,
+}
+
+impl Candidate {
+    pub fn free_cpu_millis(&self) -> i64 {
+        (self.allocatable_cpu_millis - self.requested_cpu_millis).max(0)
+    }
+
+    pub fn free_memory_bytes(&self) -> i64 {
+        (self.allocatable_memory_bytes - self.requested_memory_bytes).max(0)
+    }
+
+    /// Free CPU as a fraction of allocatable CPU, in `[0, 1]`. `0` for a node
+    /// with no allocatable CPU reported rather than dividing by zero.
+    pub fn free_cpu_fraction(&self) -> f64 {
+        if self.allocatable_cpu_millis <= 0 {
+            return 0.0;
+        }
+        self.free_cpu_millis() as f64 / self.allocatable_cpu_millis as f64
+    }
+
+    /// Free memory as a fraction of allocatable memory, in `[0, 1]`. `0` for
+    /// a node with no allocatable memory reported rather than dividing by
+    /// zero.
+    pub fn free_memory_fraction(&self) -> f64 {
+        if self.allocatable_memory_bytes <= 0 {
+            return 0.0;
+        }
+        self.free_memory_bytes() as f64 / self.allocatable_memory_bytes as f64
+    }
+}
+
+/// Sums the CPU/memory requests of every container across `pods`, in
+/// millicores and bytes respectively.
+pub fn sum_requests<'a>(pods: impl Iterator<Item = &'a Pod>) -> (i64, i64) {
+    let mut cpu_millis = 0i64;
+    let mut memory_bytes = 0i64;
+    for pod in pods {
+        for container in &pod.spec.containers {
+            if let Some(cpu) = container.resources.requests.get("cpu") {
+                cpu_millis += parse_cpu_millis(cpu);
+            }
+            if let Some(mem) = container.resources.requests.get("memory") {
+                memory_bytes += parse_memory_bytes(mem);
+            }
+        }
+    }
+    (cpu_millis, memory_bytes)
+}
+
+/// Does `candidate` satisfy a pod's `nodeSelector`?
+pub fn matches_selector(candidate: &Candidate, selector: &HashMap<String, String>) -> bool {
+    selector
+        .iter()
+        .all(|(k, v)| candidate.labels.get(k) == Some(v))
+}
+
+/// A pluggable pod placement strategy. `select` receives only healthy,
+/// selector-matching candidates and picks the best one (or none, if the
+/// list is empty).
+pub trait Scheduler: Send + Sync {
+    fn strategy_name(&self) -> &'static str;
+    fn select<'a>(&self, candidates: &'a [Candidate]) -> Option<&'a Candidate>;
+}
+
+/// Cycles through candidates in turn, ignoring load entirely.
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl RoundRobin {
+    pub fn new() -> Self {
+        Self {
+            next: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl Scheduler for RoundRobin {
+    fn strategy_name(&self) -> &'static str {
+        "round-robin"
+    }
+
+    fn select<'a>(&self, candidates: &'a [Candidate]) -> Option<&'a Candidate> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let i = self.next.fetch_add(1, Ordering::Relaxed);
+        candidates.get(i % candidates.len())
+    }
+}
+
+/// Picks the candidate with the fewest pods currently scheduled to it.
+pub struct LeastPods;
+
+impl Scheduler for LeastPods {
+    fn strategy_name(&self) -> &'static str {
+        "least-pods"
+    }
+
+    fn select<'a>(&self, candidates: &'a [Candidate]) -> Option<&'a Candidate> {
+        candidates.iter().min_by_key(|c| c.pod_count)
+    }
+}
+
+/// Picks the candidate with the most free capacity, scoring free CPU and
+/// free memory each as a fraction of that node's own allocatable amount
+/// before summing, so a node's score reflects how empty it is rather than
+/// being dominated by whichever dimension happens to have the larger raw
+/// magnitude (raw free memory bytes/MiB dwarfs raw free CPU millicores on
+/// most real nodes).
+pub struct LeastAllocated;
+
+impl Scheduler for LeastAllocated {
+    fn strategy_name(&self) -> &'static str {
+        "least-allocated"
+    }
+
+    fn select<'a>(&self, candidates: &'a [Candidate]) -> Option<&'a Candidate> {
+        candidates.iter().max_by(|a, b| {
+            let score = |c: &Candidate| c.free_cpu_fraction() + c.free_memory_fraction();
+            score(a).total_cmp(&score(b))
+        })
+    }
+}
+
+/// Resolves a `scheduler.strategy` config value to a `Scheduler` impl,
+/// falling back to the historical least-pods behavior for unknown values.
+pub fn from_name(name: &str) -> Box<dyn Scheduler> {
+    match name {
+        "round-robin" => Box::new(RoundRobin::new()),
+        "least-allocated" => Box::new(LeastAllocated),
+        _ => Box::new(LeastPods),
+    }
+}
@@ -0,0 +1,209 @@
+//! Kubernetes-style label and field selector parsing, used to filter list
+//! responses the same way a real API server would for
+//! `-l`/`--field-selector` queries. Watch (`?watch=true`) responses are not
+//! filtered by selectors.
+
+use std::collections::HashMap;
+
+use crate::models::k8s::{Node, Pod};
+
+/// A single clause of a parsed `labelSelector` string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelSelectorRequirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    In(String, Vec<String>),
+    NotIn(String, Vec<String>),
+    Exists(String),
+    NotExists(String),
+}
+
+/// Parses a `labelSelector` query value into its component requirements.
+/// Supports the standard `key=value`, `key!=value`, `key in (a,b)`,
+/// `key notin (a,b)`, bare `key` (exists), and `!key` (does not exist) forms.
+pub fn parse_label_selector(raw: &str) -> Vec<LabelSelectorRequirement> {
+    split_top_level_commas(raw)
+        .into_iter()
+        .filter_map(|clause| parse_label_clause(&clause))
+        .collect()
+}
+
+fn parse_label_clause(clause: &str) -> Option<LabelSelectorRequirement> {
+    let clause = clause.trim();
+    if clause.is_empty() {
+        return None;
+    }
+
+    if let Some(key) = clause.strip_prefix('!') {
+        return Some(LabelSelectorRequirement::NotExists(key.trim().to_string()));
+    }
+
+    if let Some((key, rest)) = split_keyword(clause, " notin ") {
+        return Some(LabelSelectorRequirement::NotIn(key, parse_set(rest)));
+    }
+    if let Some((key, rest)) = split_keyword(clause, " in ") {
+        return Some(LabelSelectorRequirement::In(key, parse_set(rest)));
+    }
+
+    if let Some((key, value)) = clause.split_once("!=") {
+        return Some(LabelSelectorRequirement::NotEquals(
+            key.trim().to_string(),
+            value.trim().to_string(),
+        ));
+    }
+    if let Some((key, value)) = clause.split_once('=') {
+        return Some(LabelSelectorRequirement::Equals(
+            key.trim().to_string(),
+            value.trim().to_string(),
+        ));
+    }
+
+    Some(LabelSelectorRequirement::Exists(clause.to_string()))
+}
+
+fn split_keyword<'a>(clause: &'a str, keyword: &str) -> Option<(String, &'a str)> {
+    clause
+        .find(keyword)
+        .map(|idx| (clause[..idx].trim().to_string(), clause[idx + keyword.len()..].trim()))
+}
+
+/// Parses the `(a, b, c)` set literal used by `in`/`notin` clauses.
+fn parse_set(rest: &str) -> Vec<String> {
+    rest.trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Splits on top-level commas only, treating anything inside `(...)` as
+/// opaque so `key in (a,b)` isn't mistaken for two separate clauses.
+fn split_top_level_commas(s: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+
+    for c in s.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' => {
+                depth -= 1;
+                current.push(c);
+            }
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
+/// Evaluates a parsed label selector against a resource's `metadata.labels`.
+pub fn matches_labels(reqs: &[LabelSelectorRequirement], labels: &HashMap<String, String>) -> bool {
+    reqs.iter().all(|req| match req {
+        LabelSelectorRequirement::Equals(k, v) => labels.get(k) == Some(v),
+        LabelSelectorRequirement::NotEquals(k, v) => labels.get(k) != Some(v),
+        LabelSelectorRequirement::In(k, values) => {
+            labels.get(k).map(|v| values.contains(v)).unwrap_or(false)
+        }
+        LabelSelectorRequirement::NotIn(k, values) => {
+            !labels.get(k).map(|v| values.contains(v)).unwrap_or(false)
+        }
+        LabelSelectorRequirement::Exists(k) => labels.contains_key(k),
+        LabelSelectorRequirement::NotExists(k) => !labels.contains_key(k),
+    })
+}
+
+/// A single `field=value` or `field!=value` clause of a parsed
+/// `fieldSelector` string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSelectorRequirement {
+    pub field: String,
+    pub value: String,
+    pub negated: bool,
+}
+
+/// Parses a `fieldSelector` query value into its component requirements.
+/// Field selectors only support equality/inequality clauses, comma
+/// separated; they have no `in`/`notin`/exists forms.
+pub fn parse_field_selector(raw: &str) -> Vec<FieldSelectorRequirement> {
+    raw.split(',')
+        .filter_map(|clause| {
+            let clause = clause.trim();
+            if clause.is_empty() {
+                return None;
+            }
+            if let Some((field, value)) = clause.split_once("!=") {
+                return Some(FieldSelectorRequirement {
+                    field: field.trim().to_string(),
+                    value: value.trim().to_string(),
+                    negated: true,
+                });
+            }
+            clause.split_once('=').map(|(field, value)| FieldSelectorRequirement {
+                field: field.trim().to_string(),
+                value: value.trim().to_string(),
+                negated: false,
+            })
+        })
+        .collect()
+}
+
+/// Evaluates a parsed field selector, resolving each clause's field name via
+/// `field_value`. A clause whose field isn't recognized never matches, same
+/// as a real API server rejecting unsupported field selectors.
+pub fn matches_fields(
+    reqs: &[FieldSelectorRequirement],
+    field_value: impl Fn(&str) -> Option<String>,
+) -> bool {
+    reqs.iter().all(|req| {
+        let actual = match field_value(&req.field) {
+            Some(v) => v,
+            None => return false,
+        };
+        let equal = actual == req.value;
+        if req.negated {
+            !equal
+        } else {
+            equal
+        }
+    })
+}
+
+/// Resolves the handful of pod fields real clients filter on:
+/// `metadata.namespace`, `metadata.name`, `spec.nodeName`, `status.phase`.
+pub fn pod_field_value(pod: &Pod, field: &str) -> Option<String> {
+    match field {
+        "metadata.namespace" => Some(pod.metadata.namespace.clone()),
+        "metadata.name" => Some(pod.metadata.name.clone()),
+        "spec.nodeName" => Some(pod.spec.node_name.clone()),
+        "status.phase" => Some(pod.status.phase.clone()),
+        _ => None,
+    }
+}
+
+/// Resolves the node equivalents of the pod fields above. Nodes are
+/// cluster-scoped (no namespace) and have no `spec` in this model, so only
+/// `metadata.name` and its `status` equivalent, the ready condition, apply.
+pub fn node_field_value(node: &Node, field: &str) -> Option<String> {
+    match field {
+        "metadata.name" => Some(node.metadata.name.clone()),
+        "status.phase" => node
+            .status
+            .conditions
+            .iter()
+            .find(|c| c.condition_type == "Ready")
+            .map(|c| c.status.clone()),
+        _ => None,
+    }
+}